@@ -0,0 +1,175 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::time::Instant;
+
+use crate::collision::OutputAction;
+use crate::ffmpeg_error::TaskError;
+use crate::i18n::t;
+use crate::verify::{self, Verdict, VerifySettings};
+use crate::{BatchTask, ProcessingState, probe_duration, process_task};
+
+// 批处理中单个任务的状态
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum TaskStatus {
+    Pending,
+    Running,
+    Done,
+    Suspicious, // 处理成功但校验发现异常
+    Failed,
+    Skipped,
+    Cancelled,
+}
+
+impl TaskStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            TaskStatus::Pending => t!("status.pending"),
+            TaskStatus::Running => t!("status.running"),
+            TaskStatus::Done => t!("status.done"),
+            TaskStatus::Suspicious => t!("status.suspicious"),
+            TaskStatus::Failed => t!("status.failed"),
+            TaskStatus::Skipped => t!("status.skipped"),
+            TaskStatus::Cancelled => t!("status.cancelled"),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct TaskResult {
+    pub status: TaskStatus,
+    pub error: Option<TaskError>,
+    pub warnings: Vec<String>, // 校验发现的问题
+    pub elapsed_secs: f64,     // 处理耗时
+    pub input_size: Option<u64>,
+    pub output_size: Option<u64>,
+    pub input_duration: Option<f64>, // 秒
+    pub output_duration: Option<f64>,
+}
+
+impl Default for TaskResult {
+    fn default() -> Self {
+        Self {
+            status: TaskStatus::Pending,
+            error: None,
+            warnings: Vec::new(),
+            elapsed_secs: 0.0,
+            input_size: None,
+            output_size: None,
+            input_duration: None,
+            output_duration: None,
+        }
+    }
+}
+
+fn file_size(path: &str) -> Option<u64> {
+    fs::metadata(path).ok().map(|m| m.len())
+}
+
+// 批处理选项
+#[derive(Clone, Default)]
+pub struct BatchOptions {
+    pub continue_on_error: bool, // 为 false 时遇到失败即停止, 其余任务标记为跳过
+    pub verify: VerifySettings,
+}
+
+// 依次处理任务并记录每个任务的结果
+pub fn run_batch(tasks: Vec<BatchTask>, state: &ProcessingState, options: &BatchOptions) {
+    *state.results.lock().unwrap() = vec![TaskResult::default(); tasks.len()];
+    *state.last_error.lock().unwrap() = None;
+
+    // 提前结束后剩余任务的状态
+    let mut halt: Option<TaskStatus> = None;
+    for (i, task) in tasks.into_iter().enumerate() {
+        if halt.is_none() && *state.cancel_requested.lock().unwrap() {
+            halt = Some(TaskStatus::Cancelled);
+        }
+        if let Some(status) = halt {
+            state.results.lock().unwrap()[i].status = status;
+            continue;
+        }
+
+        if task.output_action == OutputAction::Skip {
+            let mut results = state.results.lock().unwrap();
+            results[i].status = TaskStatus::Skipped;
+            results[i].warnings = vec![t!("batch.output_exists", task.output_path)];
+            continue;
+        }
+
+        state.results.lock().unwrap()[i].status = TaskStatus::Running;
+        *state.message.lock().unwrap() = t!("batch.processing", task.input_path);
+        *state.progress.lock().unwrap() = 0.0;
+
+        let mut result = TaskResult {
+            input_size: file_size(&task.input_path),
+            input_duration: probe_duration(&task.input_path),
+            ..TaskResult::default()
+        };
+        let output_path = task.output_path.clone();
+        let started = Instant::now();
+        let outcome = process_task(task.clone(), state).and_then(|()| {
+            // 退出码为 0 不代表输出可用, 再检查一遍输出文件
+            if !options.verify.enabled {
+                return Ok(Vec::new());
+            }
+            *state.message.lock().unwrap() = t!("batch.verifying", output_path);
+            match verify::verify_output(&task, &options.verify) {
+                Verdict::Passed => Ok(Vec::new()),
+                Verdict::Suspicious(warnings) => Ok(warnings),
+                Verdict::Failed(reason) => Err(TaskError::Verification(reason)),
+            }
+        });
+        result.elapsed_secs = started.elapsed().as_secs_f64();
+        result.output_size = file_size(&output_path);
+
+        match outcome {
+            Ok(warnings) => {
+                result.status = if warnings.is_empty() {
+                    TaskStatus::Done
+                } else {
+                    TaskStatus::Suspicious
+                };
+                result.warnings = warnings;
+                result.output_duration = probe_duration(&output_path);
+            }
+            Err(TaskError::Cancelled) => {
+                halt = Some(TaskStatus::Cancelled);
+                result.status = TaskStatus::Cancelled;
+            }
+            Err(e) => {
+                if !options.continue_on_error {
+                    halt = Some(TaskStatus::Skipped);
+                }
+                *state.last_error.lock().unwrap() = Some(e.clone());
+                result.status = TaskStatus::Failed;
+                result.error = Some(e);
+            }
+        }
+        state.results.lock().unwrap()[i] = result;
+    }
+
+    // 处理完成后更新状态
+    *state.message.lock().unwrap() = summary(&state.results.lock().unwrap());
+    *state.progress.lock().unwrap() = 0.0;
+}
+
+// 各状态的任务数量, 如 "处理完成: 完成 3, 失败 1"
+pub fn summary(results: &[TaskResult]) -> String {
+    let counts: Vec<String> = [
+        TaskStatus::Done,
+        TaskStatus::Suspicious,
+        TaskStatus::Failed,
+        TaskStatus::Skipped,
+        TaskStatus::Cancelled,
+    ]
+    .iter()
+    .map(|&status| {
+        (
+            status,
+            results.iter().filter(|r| r.status == status).count(),
+        )
+    })
+    .filter(|&(_, count)| count > 0)
+    .map(|(status, count)| format!("{} {}", status.label(), count))
+    .collect();
+    t!("batch.summary", counts.join(", "))
+}
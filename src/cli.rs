@@ -0,0 +1,298 @@
+use std::path::Path;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+
+use crate::batch::{self, TaskStatus};
+use crate::collision::{self, CollisionPolicy, OutputAction};
+use crate::command;
+use crate::container;
+use crate::i18n::t;
+use crate::preset::{self, Preset};
+use crate::watch::{self, WatchJob, WatchStatus};
+use crate::{
+    AppConfig, BatchTask, DEFAULT_TEMPLATE, ProcessingState, TaskParams, default_config_path,
+    generate_output_path, logging, read_config, report, template,
+};
+
+#[derive(Default)]
+struct CliOptions {
+    help: bool,
+    list_presets: bool,
+    watch: bool,
+    preset: Option<String>,
+    start_time: Option<String>,
+    end_time: Option<String>,
+    speed: Option<f64>,
+    reverse: bool,
+    drop_audio: bool,
+    output_dir: Option<String>,
+    report: Option<String>,
+    on_conflict: Option<CollisionPolicy>,
+    files: Vec<String>,
+}
+
+// 没有命令行参数时返回 None, 由调用方启动图形界面; 否则返回进程退出码
+pub fn run(args: &[String]) -> Option<i32> {
+    if args.is_empty() {
+        return None;
+    }
+    let code = match parse_args(args) {
+        Ok(options) => execute(options),
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, t!("cli.usage"));
+            2
+        }
+    };
+    Some(code)
+}
+
+fn parse_args(args: &[String]) -> Result<CliOptions, String> {
+    let mut options = CliOptions::default();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = |name: &str| {
+            iter.next()
+                .cloned()
+                .ok_or_else(|| t!("cli.missing_value", name))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => options.help = true,
+            "--list-presets" => options.list_presets = true,
+            "--watch" => options.watch = true,
+            "--preset" => options.preset = Some(value(arg)?),
+            "--start" => options.start_time = Some(value(arg)?),
+            "--end" => options.end_time = Some(value(arg)?),
+            "--speed" => {
+                let text = value(arg)?;
+                let factor = text.parse().map_err(|_| t!("cli.invalid_speed", text))?;
+                options.speed = Some(factor);
+            }
+            "--reverse" => options.reverse = true,
+            "--drop-audio" => options.drop_audio = true,
+            "--output-dir" => options.output_dir = Some(value(arg)?),
+            "--report" => options.report = Some(value(arg)?),
+            "--on-conflict" => {
+                options.on_conflict = Some(match value(arg)?.as_str() {
+                    "overwrite" => CollisionPolicy::Overwrite,
+                    "skip" => CollisionPolicy::Skip,
+                    "number" => CollisionPolicy::AppendCounter,
+                    other => return Err(t!("cli.unknown_conflict", other)),
+                })
+            }
+            _ if arg.starts_with('-') => return Err(t!("cli.unknown_option", arg)),
+            _ => options.files.push(arg.clone()),
+        }
+    }
+    Ok(options)
+}
+
+fn execute(options: CliOptions) -> i32 {
+    if options.help {
+        println!("{}", t!("cli.usage"));
+        return 0;
+    }
+
+    let config = read_config(&default_config_path()).unwrap_or_default();
+    if options.list_presets {
+        for preset in &config.presets {
+            println!("{}", preset.name);
+        }
+        return 0;
+    }
+    if options.watch {
+        return watch_folder(options, config);
+    }
+    if options.files.is_empty() {
+        eprintln!("{}\n\n{}", t!("cli.no_input"), t!("cli.usage"));
+        return 2;
+    }
+
+    let preset = match find_preset(&config, options.preset.as_deref()) {
+        Ok(preset) => preset,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+
+    let mut params = TaskParams::default();
+    preset.apply_to(&mut params);
+    if let Some(start) = options.start_time {
+        params.start_time = start;
+    }
+    if let Some(end) = options.end_time {
+        params.end_time = end;
+    }
+    if let Some(factor) = options.speed {
+        params.speed.factor = factor;
+    }
+    params.speed.reverse |= options.reverse;
+    params.speed.drop_audio |= options.drop_audio;
+    let output_dir = options.output_dir.unwrap_or(preset.output_dir);
+    if let Err(e) = template::validate(&preset.output_template) {
+        eprintln!("{}", t!("cli.invalid_template", e));
+        return 2;
+    }
+
+    let mut tasks: Vec<BatchTask> = options
+        .files
+        .iter()
+        .enumerate()
+        .map(|(i, input)| {
+            let segment = options.files[..=i].iter().filter(|f| *f == input).count();
+            let template_input = template::TemplateInput {
+                input_path: input,
+                params: &params,
+                index: i + 1,
+                segment,
+            };
+            let output_path = generate_output_path(
+                &template_input,
+                &output_dir,
+                &preset.output_template,
+                &config.sanitize,
+            );
+            BatchTask {
+                input_path: input.clone(),
+                log_path: logging::log_path_for(&output_path, &config.log_dir),
+                output_path,
+                params: params.clone(),
+                global_args: config.global_args.clone(),
+                output_action: OutputAction::default(),
+            }
+        })
+        .collect();
+
+    // 命令行模式无法询问, 未指定时跳过已存在的输出
+    let conflicts = collision::find_conflicts(&tasks);
+    let policy = match options.on_conflict.unwrap_or(config.collision_policy) {
+        CollisionPolicy::Ask => CollisionPolicy::Skip,
+        policy => policy,
+    };
+    if !conflicts.is_empty() {
+        eprintln!("{}", t!("cli.conflicts", policy.label()));
+        for conflict in &conflicts {
+            eprintln!("  {}", conflict.description());
+        }
+    }
+    collision::resolve(&mut tasks, policy);
+
+    // 时间或参数无效、容器与编码不兼容时 ffmpeg 必然失败, 提前退出
+    let mut incompatible = false;
+    for task in &tasks {
+        if let Err(e) = command::build_ffmpeg_args(task) {
+            eprintln!("{}: {}", task.input_path, e);
+            incompatible = true;
+        }
+        for problem in container::check(task) {
+            eprintln!("{}: {}", task.input_path, problem);
+            incompatible = true;
+        }
+    }
+    if incompatible {
+        return 2;
+    }
+
+    // 命令行模式下总是处理完全部文件
+    let state = ProcessingState::default();
+    let batch_options = batch::BatchOptions {
+        continue_on_error: true,
+        verify: config.verify.clone(),
+    };
+    batch::run_batch(tasks.clone(), &state, &batch_options);
+
+    let results = state.results.lock().unwrap().clone();
+    for (task, result) in tasks.iter().zip(&results) {
+        println!("[{}] {}", result.status.label(), task.input_path);
+        if let Some(e) = &result.error {
+            eprintln!("  {}", t!("common.error", e));
+            if e.explanation() != e.to_string() {
+                eprintln!("  {}", e.explanation());
+            }
+            eprintln!("  {}", t!("common.suggestion", e.suggestion()));
+        }
+        for warning in &result.warnings {
+            eprintln!("  {}", t!("common.warning", warning));
+        }
+    }
+    println!("{}", state.message.lock().unwrap());
+
+    if let Some(path) = options.report {
+        if let Err(e) = report::export_report(Path::new(&path), &tasks, &results) {
+            eprintln!("{}", t!("common.error", e));
+            return 1;
+        }
+        println!("{}", t!("report.exported", path));
+    }
+
+    // 命令行模式下出错不会停止批处理, 跳过的只有输出已存在的任务
+    if results.iter().all(|r| {
+        matches!(
+            r.status,
+            TaskStatus::Done | TaskStatus::Suspicious | TaskStatus::Skipped
+        )
+    }) {
+        0
+    } else {
+        1
+    }
+}
+
+// 未指定预设时使用默认编码设置, 输出到配置中的输出目录
+fn find_preset(config: &AppConfig, name: Option<&str>) -> Result<Preset, String> {
+    match name.filter(|n| !n.is_empty()) {
+        Some(name) => preset::find_preset(&config.presets, name)
+            .cloned()
+            .ok_or_else(|| t!("cli.preset_not_found", name)),
+        None => Ok(Preset {
+            output_dir: config.output_dir.clone(),
+            output_template: DEFAULT_TEMPLATE.to_owned(),
+            ..Preset::default()
+        }),
+    }
+}
+
+// 按配置中的监视文件夹设置持续处理新文件, 直到进程被终止
+fn watch_folder(options: CliOptions, config: AppConfig) -> i32 {
+    let mut settings = config.watch.clone();
+    if let Some(dir) = options.files.first() {
+        settings.input_dir = dir.clone();
+    }
+    if let Some(name) = options.preset {
+        settings.preset = name;
+    }
+    if let Some(dir) = options.output_dir {
+        settings.output_dir = dir;
+    }
+    let preset = match find_preset(&config, Some(&settings.preset)) {
+        Ok(preset) => preset,
+        Err(e) => {
+            eprintln!("{}", e);
+            return 2;
+        }
+    };
+    if let Err(e) = template::validate(&preset.output_template) {
+        eprintln!("{}", t!("cli.invalid_template", e));
+        return 2;
+    }
+    let job = WatchJob {
+        settings,
+        preset,
+        sanitize: config.sanitize,
+        global_args: config.global_args,
+        log_dir: config.log_dir,
+        verify: config.verify,
+        collision_policy: options.on_conflict.unwrap_or(config.collision_policy),
+    };
+    if let Err(e) = watch::validate(&job) {
+        eprintln!("{}", e);
+        return 2;
+    }
+    watch::run(
+        &job,
+        &AtomicBool::new(false),
+        &Mutex::new(WatchStatus::default()),
+        &ProcessingState::default(),
+    );
+    0
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::Path;
+
+use crate::i18n::t;
+use crate::{BatchTask, logging};
+
+// 输出文件已存在时的处理策略
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum CollisionPolicy {
+    #[default]
+    Ask,
+    Overwrite,
+    Skip,
+    AppendCounter,
+}
+
+impl CollisionPolicy {
+    pub const ALL: [CollisionPolicy; 4] = [
+        CollisionPolicy::Ask,
+        CollisionPolicy::Overwrite,
+        CollisionPolicy::Skip,
+        CollisionPolicy::AppendCounter,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            CollisionPolicy::Ask => t!("collision.ask"),
+            CollisionPolicy::Overwrite => t!("collision.overwrite"),
+            CollisionPolicy::Skip => t!("collision.skip"),
+            CollisionPolicy::AppendCounter => t!("collision.counter"),
+        }
+    }
+}
+
+// 单个任务对输出文件的处理方式, 决定传给 ffmpeg 的 -y / -n
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum OutputAction {
+    // 输出不存在, 使用 -n 确保不会意外覆盖
+    #[default]
+    Create,
+    Overwrite,
+    Skip,
+}
+
+// 冲突原因
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ConflictReason {
+    Exists,
+    // 与同一批次中前面的任务输出到同一文件
+    Duplicate(usize),
+}
+
+pub struct Conflict {
+    pub index: usize,
+    pub output_path: String,
+    pub reason: ConflictReason,
+}
+
+impl Conflict {
+    pub fn description(&self) -> String {
+        match self.reason {
+            ConflictReason::Exists => t!("collision.exists", self.output_path),
+            ConflictReason::Duplicate(other) => {
+                t!("collision.duplicate", self.output_path, other + 1)
+            }
+        }
+    }
+}
+
+// 开始处理前列出所有输出冲突
+pub fn find_conflicts(tasks: &[BatchTask]) -> Vec<Conflict> {
+    let mut conflicts = Vec::new();
+    for (i, task) in tasks.iter().enumerate() {
+        let reason = match tasks[..i]
+            .iter()
+            .position(|t| t.output_path == task.output_path)
+        {
+            Some(other) => ConflictReason::Duplicate(other),
+            None if Path::new(&task.output_path).exists() => ConflictReason::Exists,
+            None => continue,
+        };
+        conflicts.push(Conflict {
+            index: i,
+            output_path: task.output_path.clone(),
+            reason,
+        });
+    }
+    conflicts
+}
+
+// 按策略处理冲突. 同一批次内的重复输出总是自动编号, 避免任务互相覆盖
+pub fn resolve(tasks: &mut [BatchTask], policy: CollisionPolicy) {
+    let mut claimed: HashSet<String> = HashSet::new();
+    for task in tasks.iter_mut() {
+        task.output_action = OutputAction::Create;
+        if claimed.contains(&task.output_path) {
+            renumber(task, &claimed);
+        } else if Path::new(&task.output_path).exists() {
+            match policy {
+                CollisionPolicy::Overwrite => task.output_action = OutputAction::Overwrite,
+                CollisionPolicy::AppendCounter => renumber(task, &claimed),
+                // 未选择策略时保守处理
+                CollisionPolicy::Skip | CollisionPolicy::Ask => {
+                    task.output_action = OutputAction::Skip
+                }
+            }
+        }
+        claimed.insert(task.output_path.clone());
+    }
+}
+
+// 在主文件名后追加 _1、_2 ... 直到文件名可用, 日志文件随之改名
+fn renumber(task: &mut BatchTask, claimed: &HashSet<String>) {
+    let path = Path::new(&task.output_path);
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    let mut n = 1;
+    let new_path = loop {
+        let candidate = path
+            .with_file_name(format!("{}_{}{}", stem, n, extension))
+            .to_string_lossy()
+            .into_owned();
+        if !claimed.contains(&candidate) && !Path::new(&candidate).exists() {
+            break candidate;
+        }
+        n += 1;
+    };
+
+    logging::log(t!("collision.renamed", new_path));
+    if !task.log_path.is_empty() {
+        let log_dir = Path::new(&task.log_path)
+            .parent()
+            .map(|p| p.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        task.log_path = logging::log_path_for(&new_path, &log_dir);
+    }
+    task.output_path = new_path;
+}
@@ -0,0 +1,358 @@
+use serde::{Deserialize, Serialize};
+
+use crate::BatchTask;
+use crate::collision::OutputAction;
+use crate::container;
+use crate::i18n::t;
+use crate::media;
+use crate::preset::TrimMode;
+use crate::timecode::{self, format_seconds};
+
+// 用户追加的 ffmpeg 参数, 以命令行形式书写
+#[derive(Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExtraArgs {
+    pub input: String,  // 放在 -i 之前
+    pub output: String, // 放在输出文件之前
+}
+
+// 由程序自身管理、不允许在追加参数中出现的选项
+const MANAGED_OPTIONS: &[&str] = &["-i", "-y", "-n"];
+
+// 不带取值的常用选项, 用于检查末尾选项是否缺少取值
+const FLAG_OPTIONS: &[&str] = &[
+    "-an",
+    "-vn",
+    "-sn",
+    "-dn",
+    "-re",
+    "-shortest",
+    "-copyts",
+    "-start_at_zero",
+    "-hide_banner",
+    "-nostdin",
+    "-stats",
+    "-nostats",
+    "-autorotate",
+    "-noautorotate",
+];
+
+// 按 shell 规则拆分参数, 支持单双引号和反斜杠转义
+pub fn split_args(line: &str) -> Result<Vec<String>, String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_arg = false;
+    let mut quote: Option<char> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some('"'), '\\') | (None, '\\') => match chars.next() {
+                Some(next) => {
+                    current.push(next);
+                    in_arg = true;
+                }
+                None => return Err(t!("args.trailing_escape").to_string()),
+            },
+            (Some(_), c) => current.push(c),
+            (None, '\'' | '"') => {
+                quote = Some(c);
+                in_arg = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_arg {
+                    args.push(std::mem::take(&mut current));
+                    in_arg = false;
+                }
+            }
+            (None, c) => {
+                current.push(c);
+                in_arg = true;
+            }
+        }
+    }
+
+    if let Some(q) = quote {
+        return Err(t!("args.unclosed_quote", q));
+    }
+    if in_arg {
+        args.push(current);
+    }
+    Ok(args)
+}
+
+// 拆分并检查追加参数
+pub fn parse_extra_args(line: &str) -> Result<Vec<String>, String> {
+    let args = split_args(line)?;
+    if let Some(arg) = args.iter().find(|a| MANAGED_OPTIONS.contains(&a.as_str())) {
+        return Err(t!("args.managed", arg));
+    }
+    if let Some(last) = args.last()
+        && last.starts_with('-')
+        && last.len() > 1
+        && last.parse::<f64>().is_err()
+        && !FLAG_OPTIONS.contains(&last.as_str())
+    {
+        return Err(t!("args.missing_value", last));
+    }
+    Ok(args)
+}
+
+// 依次检查全局和单个任务的追加参数
+pub fn validate_extra_args(global: &ExtraArgs, task: &ExtraArgs) -> Result<(), String> {
+    for (input_key, output_key, args) in [
+        ("args.global_input", "args.global_output", global),
+        ("args.file_input", "args.file_output", task),
+    ] {
+        parse_extra_args(&args.input).map_err(|e| t!(input_key, e))?;
+        parse_extra_args(&args.output).map_err(|e| t!(output_key, e))?;
+    }
+    Ok(())
+}
+
+// 将参数列表格式化为可复制到终端执行的命令
+pub fn format_command(args: &[String]) -> String {
+    let mut line = String::from("ffmpeg");
+    for arg in args {
+        line.push(' ');
+        if !arg.is_empty()
+            && arg
+                .chars()
+                .all(|c| c.is_alphanumeric() || "-_./:=,+%@".contains(c))
+        {
+            line.push_str(arg);
+        } else {
+            line.push('\'');
+            line.push_str(&arg.replace('\'', r"'\''"));
+            line.push('\'');
+        }
+    }
+    line
+}
+
+// 追加的输出参数中是否包含某个开关, 如 -an
+pub fn has_output_flag(task: &BatchTask, flag: &str) -> bool {
+    [&task.global_args.output, &task.params.extra_args.output]
+        .iter()
+        .filter_map(|args| split_args(args).ok())
+        .any(|args| args.iter().any(|a| a == flag))
+}
+
+// 输出中是否去掉了音频: 变速设置中去掉音频或追加了 -an
+pub fn drops_audio(task: &BatchTask) -> bool {
+    task.params.speed.drop_audio || has_output_flag(task, "-an")
+}
+
+// 根据任务参数生成完整的 ffmpeg 参数列表
+pub fn build_ffmpeg_args(task: &BatchTask) -> Result<Vec<String>, String> {
+    let params = &task.params;
+    let enc = &params.encoding;
+    let trim = timecode::task_trim(&params.start_time, &params.end_time, &task.input_path)?;
+    validate_extra_args(&task.global_args, &params.extra_args)?;
+    let speed = &params.speed;
+    speed.validate(trim.output_duration(media::info(&task.input_path).duration))?;
+    // 输出已存在时: -y 覆盖, -n 直接失败, 避免 ffmpeg 等待终端输入
+    let mut args: Vec<String> = vec![match task.output_action {
+        OutputAction::Overwrite => "-y".to_string(),
+        OutputAction::Create | OutputAction::Skip => "-n".to_string(),
+    }];
+    let mut trim_args = Vec::new();
+    if let Some(start) = trim.start {
+        trim_args.extend(["-ss".to_string(), format_seconds(start)]);
+    }
+    if let Some(end) = trim.end {
+        trim_args.extend(["-to".to_string(), format_seconds(end)]);
+    }
+
+    // 快速裁剪: 作为输入参数按关键帧定位.
+    // 变速和倒放的滤镜在输出端裁剪之前执行, 必须先在输入端裁剪;
+    // 此时所有流都重新编码, 输入端定位同样精确到帧
+    let trim_input = params.trim_mode == TrimMode::Fast || speed.changes_timing();
+    if trim_input {
+        args.extend(trim_args.iter().cloned());
+    }
+    // 追加的输入参数: 先全局, 后单个文件
+    args.extend(parse_extra_args(&task.global_args.input)?);
+    args.extend(parse_extra_args(&params.extra_args.input)?);
+    args.extend(["-i".to_string(), task.input_path.clone()]);
+    if !trim_input {
+        args.extend(trim_args);
+    }
+
+    // 视频编码, 纯音频容器直接去掉视频流
+    let audio_only = container::is_audio_only(&container::of_path(&task.output_path));
+    if audio_only && speed.drop_audio {
+        return Err(t!("speed.nothing_left").to_string());
+    }
+    // 变速和倒放通过滤镜实现, 需要重新编码
+    if speed.changes_timing() {
+        if !audio_only && enc.video_codec == "copy" {
+            return Err(t!("speed.video_copy").to_string());
+        }
+        if !speed.drop_audio && enc.audio_codec == "copy" {
+            return Err(t!("speed.audio_copy").to_string());
+        }
+    }
+    if audio_only {
+        args.push("-vn".to_string());
+    } else {
+        args.extend(["-c:v".to_string(), enc.video_codec.clone()]);
+    }
+    if !audio_only && enc.video_codec != "copy" {
+        if let Some(crf) = enc.crf {
+            args.extend(["-crf".to_string(), crf.to_string()]);
+        }
+        let mut filters = Vec::new();
+        if enc.width.is_some() || enc.height.is_some() {
+            // 未指定的一边按比例缩放
+            let w = enc.width.map_or("-2".to_string(), |w| w.to_string());
+            let h = enc.height.map_or("-2".to_string(), |h| h.to_string());
+            filters.push(format!("scale={}:{}", w, h));
+        }
+        if !enc.video_filters.trim().is_empty() {
+            filters.push(enc.video_filters.trim().to_string());
+        }
+        filters.extend(speed.video_filters());
+        if !filters.is_empty() {
+            args.extend(["-vf".to_string(), filters.join(",")]);
+        }
+    }
+
+    // 音频编码
+    if speed.drop_audio {
+        args.push("-an".to_string());
+    } else {
+        args.extend(["-c:a".to_string(), enc.audio_codec.clone()]);
+        if enc.audio_codec != "copy"
+            && let Some(bitrate) = enc.audio_bitrate
+        {
+            args.extend(["-b:a".to_string(), format!("{}k", bitrate)]);
+        }
+        let audio_filters = speed.audio_filters();
+        if !audio_filters.is_empty() {
+            args.extend(["-af".to_string(), audio_filters.join(",")]);
+        }
+    }
+
+    // 添加旋转元数据
+    if params.rotation != 0 && !audio_only {
+        args.extend([
+            "-metadata:s:v".to_string(),
+            format!("rotate={}", params.rotation),
+        ]);
+    }
+
+    // 追加的输出参数
+    args.extend(parse_extra_args(&task.global_args.output)?);
+    args.extend(parse_extra_args(&params.extra_args.output)?);
+
+    args.push(task.output_path.clone());
+    Ok(args)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TaskParams;
+    use crate::speed::SpeedSettings;
+
+    fn task(start: &str, end: &str, speed: SpeedSettings) -> BatchTask {
+        let mut params = TaskParams {
+            start_time: start.to_string(),
+            end_time: end.to_string(),
+            trim_mode: TrimMode::Accurate,
+            speed,
+            ..TaskParams::default()
+        };
+        params.encoding.video_codec = "libx264".to_string();
+        params.encoding.audio_codec = "aac".to_string();
+        BatchTask {
+            input_path: "missing-input.mp4".to_string(),
+            output_path: "out.mp4".to_string(),
+            params,
+            global_args: ExtraArgs::default(),
+            log_path: String::new(),
+            output_action: OutputAction::Overwrite,
+        }
+    }
+
+    fn speed(factor: f64, reverse: bool) -> SpeedSettings {
+        SpeedSettings {
+            factor,
+            reverse,
+            ..SpeedSettings::default()
+        }
+    }
+
+    fn position(args: &[String], flag: &str) -> Option<usize> {
+        args.iter().position(|a| a == flag)
+    }
+
+    fn value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+        Some(args[position(args, flag)? + 1].as_str())
+    }
+
+    #[test]
+    fn accurate_trim_is_an_output_option() {
+        let args = build_ffmpeg_args(&task("10", "20", SpeedSettings::default())).unwrap();
+        let input = position(&args, "-i").unwrap();
+        assert!(position(&args, "-ss").unwrap() > input);
+        assert!(position(&args, "-to").unwrap() > input);
+        assert_eq!(value(&args, "-vf"), None);
+        assert_eq!(value(&args, "-af"), None);
+    }
+
+    #[test]
+    fn speed_change_trims_the_input_before_filtering() {
+        let args = build_ffmpeg_args(&task("10", "20", speed(2.0, false))).unwrap();
+        let input = position(&args, "-i").unwrap();
+        assert!(position(&args, "-ss").unwrap() < input);
+        assert!(position(&args, "-to").unwrap() < input);
+        assert_eq!(value(&args, "-ss"), Some("00:00:10.000"));
+        assert_eq!(value(&args, "-to"), Some("00:00:20.000"));
+        assert_eq!(value(&args, "-vf"), Some("setpts=PTS/2"));
+        assert_eq!(value(&args, "-af"), Some("atempo=2"));
+    }
+
+    #[test]
+    fn reverse_only_buffers_the_trimmed_clip() {
+        let args = build_ffmpeg_args(&task("10", "20", speed(1.0, true))).unwrap();
+        assert!(position(&args, "-ss").unwrap() < position(&args, "-i").unwrap());
+        assert_eq!(value(&args, "-vf"), Some("reverse"));
+        assert_eq!(value(&args, "-af"), Some("areverse"));
+
+        assert_eq!(
+            build_ffmpeg_args(&task("10", "100", speed(1.0, true))),
+            Err(t!("speed.reverse_too_long", "00:01:30.000", "00:01:00.000"))
+        );
+    }
+
+    #[test]
+    fn drop_audio_removes_audio_options() {
+        let mut task = task("", "", speed(0.5, false));
+        task.params.speed.drop_audio = true;
+        let args = build_ffmpeg_args(&task).unwrap();
+        assert!(position(&args, "-an").is_some());
+        assert_eq!(value(&args, "-c:a"), None);
+        assert_eq!(value(&args, "-af"), None);
+        assert_eq!(value(&args, "-vf"), Some("setpts=PTS/0.5"));
+        assert!(drops_audio(&task));
+    }
+
+    #[test]
+    fn timing_changes_need_reencoding() {
+        let mut task = task("", "", speed(2.0, false));
+        task.params.encoding.video_codec = "copy".to_string();
+        assert_eq!(
+            build_ffmpeg_args(&task),
+            Err(t!("speed.video_copy").to_string())
+        );
+        task.params.encoding.video_codec = "libx264".to_string();
+        task.params.encoding.audio_codec = "copy".to_string();
+        assert_eq!(
+            build_ffmpeg_args(&task),
+            Err(t!("speed.audio_copy").to_string())
+        );
+    }
+}
@@ -0,0 +1,141 @@
+use std::path::Path;
+
+use crate::command::{drops_audio, has_output_flag};
+use crate::i18n::t;
+use crate::{BatchTask, media};
+
+// 可选的输出容器, 空字符串表示沿用源文件扩展名
+pub const CONTAINERS: &[&str] = &["mp4", "mkv", "mov", "webm", "ts", "m4a", "mp3", "avi"];
+
+// 容器支持的编码(ffprobe 中的编码名称), None 表示不限制
+struct Support {
+    video: Option<&'static [&'static str]>,
+    audio: Option<&'static [&'static str]>,
+}
+
+fn support(container: &str) -> Option<Support> {
+    let (video, audio): (Option<&[&str]>, Option<&[&str]>) = match container {
+        "mp4" => (
+            Some(&["h264", "hevc", "av1", "vp9", "mpeg4", "mpeg2video"]),
+            Some(&["aac", "mp3", "ac3", "eac3", "opus", "flac", "alac"]),
+        ),
+        "mov" => (
+            Some(&["h264", "hevc", "prores", "mpeg4", "mjpeg", "mpeg2video"]),
+            Some(&[
+                "aac",
+                "mp3",
+                "ac3",
+                "alac",
+                "pcm_s16le",
+                "pcm_s24le",
+                "pcm_s16be",
+                "pcm_s24be",
+                "pcm_f32le",
+            ]),
+        ),
+        "webm" => (Some(&["vp8", "vp9", "av1"]), Some(&["opus", "vorbis"])),
+        "ts" => (
+            Some(&["h264", "hevc", "mpeg2video", "mpeg4"]),
+            Some(&["aac", "mp3", "mp2", "ac3", "eac3", "opus"]),
+        ),
+        "m4a" => (Some(&[]), Some(&["aac", "alac"])),
+        "mp3" => (Some(&[]), Some(&["mp3"])),
+        "avi" => (
+            Some(&["h264", "mpeg4", "mjpeg", "msmpeg4v3"]),
+            Some(&["mp3", "ac3", "pcm_s16le"]),
+        ),
+        "mkv" => (None, None),
+        _ => return None,
+    };
+    Some(Support { video, audio })
+}
+
+// 只能包含音频的容器, 处理时自动去掉视频流
+pub fn is_audio_only(container: &str) -> bool {
+    support(container).is_some_and(|s| s.video.is_some_and(|v| v.is_empty()))
+}
+
+// 输出文件的容器由扩展名决定
+pub fn of_path(path: &str) -> String {
+    Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+// 编码器名称对应的编码, 如 libx264 输出 h264
+fn encoded_codec(encoder: &str) -> &str {
+    match encoder {
+        "libx264" | "h264_nvenc" | "h264_qsv" | "h264_videotoolbox" => "h264",
+        "libx265" | "hevc_nvenc" | "hevc_qsv" | "hevc_videotoolbox" => "hevc",
+        "libvpx" => "vp8",
+        "libvpx-vp9" => "vp9",
+        "libaom-av1" | "libsvtav1" | "librav1e" => "av1",
+        "prores_ks" => "prores",
+        "libmp3lame" => "mp3",
+        "libopus" => "opus",
+        "libvorbis" => "vorbis",
+        "libfdk_aac" => "aac",
+        other => other,
+    }
+}
+
+// 开始处理前检查输出容器能否容纳所选的音视频编码
+pub fn check(task: &BatchTask) -> Vec<String> {
+    let container = of_path(&task.output_path);
+    let Some(support) = support(&container) else {
+        return Vec::new();
+    };
+    let enc = &task.params.encoding;
+    let mut problems = Vec::new();
+    // 复制流时需要知道源文件的编码
+    let needs_probe = enc.video_codec == "copy" || enc.audio_codec == "copy";
+    let info = if needs_probe {
+        media::info(&task.input_path)
+    } else {
+        media::MediaInfo::default()
+    };
+
+    let mut check_stream =
+        |key: &'static str, encoder: &str, source: Option<String>, allowed: &[&str]| {
+            let codec = if encoder == "copy" {
+                match source {
+                    Some(codec) => codec,
+                    None => return,
+                }
+            } else {
+                encoded_codec(encoder).to_string()
+            };
+            if !allowed.contains(&codec.as_str()) {
+                let source_note = if encoder == "copy" {
+                    t!("container.copy_note")
+                } else {
+                    ""
+                };
+                problems.push(t!(key, container.to_uppercase(), codec, source_note));
+            }
+        };
+
+    if let Some(allowed) = support.video
+        && !allowed.is_empty()
+        && !has_output_flag(task, "-vn")
+    {
+        check_stream(
+            "container.unsupported_video",
+            &enc.video_codec,
+            info.video_codec.clone(),
+            allowed,
+        );
+    }
+    if let Some(allowed) = support.audio
+        && !drops_audio(task)
+    {
+        check_stream(
+            "container.unsupported_audio",
+            &enc.audio_codec,
+            info.audio_codec.clone(),
+            allowed,
+        );
+    }
+    problems
+}
@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::SystemTime;
+
+use crate::media;
+
+// 内容指纹读取文件开头和结尾各 1 MiB
+const CHUNK_SIZE: u64 = 1024 * 1024;
+
+// 相似度检测在视频的这些位置各取一帧
+const SAMPLE_POSITIONS: [f64; 5] = [0.1, 0.3, 0.5, 0.7, 0.9];
+
+// 时长相差超过该比例的视频不视为相似
+const MAX_DURATION_DIFFERENCE: f64 = 0.05;
+
+// 重复检测设置
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuplicateSettings {
+    pub perceptual: bool, // 是否比较抽样画面, 需要为每个文件调用多次 ffmpeg
+    pub threshold: u32,   // 画面哈希平均不同的位数(0-64)不超过该值时视为相似
+}
+
+impl Default for DuplicateSettings {
+    fn default() -> Self {
+        Self {
+            perceptual: false,
+            threshold: 8,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MatchKind {
+    Identical, // 大小和首尾内容相同
+    Similar,   // 抽样画面相似, 如重新编码的同一段视频
+}
+
+// 一组内容相同或相似的文件
+#[derive(Clone, Debug)]
+pub struct Group {
+    pub kind: MatchKind,
+    pub paths: Vec<String>,
+}
+
+// 文件大小和修改时间不变时沿用缓存的指纹
+type Signature = (u64, Option<SystemTime>);
+
+#[derive(Clone)]
+struct Fingerprint {
+    signature: Signature,
+    hash: Option<u64>,                // 读取失败时为 None
+    frames: Option<Option<Vec<u64>>>, // 未计算时为 None, 无法取帧时为 Some(None)
+}
+
+static CACHE: Mutex<Option<HashMap<String, Fingerprint>>> = Mutex::new(None);
+
+fn signature(path: &str) -> Option<Signature> {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.len(), metadata.modified().ok()))
+}
+
+// FNV-1a, 只用于比较, 不需要抗碰撞
+fn fnv1a(hash: u64, data: &[u8]) -> u64 {
+    data.iter()
+        .fold(hash, |h, b| (h ^ *b as u64).wrapping_mul(0x100000001b3))
+}
+
+// 文件首尾内容的哈希, 大文件只读取 2 MiB
+fn content_hash(path: &str, size: u64) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    let mut hash = fnv1a(0xcbf29ce484222325, &size.to_le_bytes());
+    let mut buffer = Vec::new();
+    file.by_ref()
+        .take(CHUNK_SIZE)
+        .read_to_end(&mut buffer)
+        .ok()?;
+    hash = fnv1a(hash, &buffer);
+    if size > CHUNK_SIZE {
+        let tail = (size - CHUNK_SIZE).max(CHUNK_SIZE);
+        file.seek(SeekFrom::Start(tail)).ok()?;
+        buffer.clear();
+        file.take(CHUNK_SIZE).read_to_end(&mut buffer).ok()?;
+        hash = fnv1a(hash, &buffer);
+    }
+    Some(hash)
+}
+
+// 取一帧缩放为 9x8 灰度图, 比较相邻像素得到 64 位差异哈希
+fn frame_hash(path: &str, seconds: f64) -> Option<u64> {
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-ss", &format!("{:.3}", seconds), "-i", path])
+        .args([
+            "-frames:v",
+            "1",
+            "-vf",
+            "scale=9:8:flags=area,format=gray",
+            "-f",
+            "rawvideo",
+            "-",
+        ])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let pixels = output.stdout;
+    if !output.status.success() || pixels.len() < 72 {
+        return None;
+    }
+    let mut hash = 0u64;
+    for row in pixels[..72].chunks(9) {
+        for x in 0..8 {
+            hash = (hash << 1) | (row[x] < row[x + 1]) as u64;
+        }
+    }
+    Some(hash)
+}
+
+fn frame_hashes(path: &str) -> Option<Vec<u64>> {
+    let duration = media::info(path).duration.filter(|d| *d > 0.0)?;
+    SAMPLE_POSITIONS
+        .iter()
+        .map(|p| frame_hash(path, duration * p))
+        .collect()
+}
+
+fn fingerprint(path: &str, perceptual: bool) -> Option<Fingerprint> {
+    let signature = signature(path)?;
+    let cached = CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .get(path)
+        .filter(|f| f.signature == signature)
+        .cloned();
+    let mut fingerprint = cached.unwrap_or_else(|| Fingerprint {
+        signature,
+        hash: content_hash(path, signature.0),
+        frames: None,
+    });
+    if perceptual && fingerprint.frames.is_none() {
+        fingerprint.frames = Some(frame_hashes(path));
+    }
+    CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(path.to_string(), fingerprint.clone());
+    Some(fingerprint)
+}
+
+// 各抽样帧哈希的平均汉明距离
+fn frame_distance(a: &[u64], b: &[u64]) -> u32 {
+    let total: u32 = a.iter().zip(b).map(|(x, y)| (x ^ y).count_ones()).sum();
+    total / a.len().max(1) as u32
+}
+
+fn similar_duration(a: &str, b: &str) -> bool {
+    match (media::info(a).duration, media::info(b).duration) {
+        (Some(a), Some(b)) => (a - b).abs() <= a.max(b) * MAX_DURATION_DIFFERENCE,
+        _ => false,
+    }
+}
+
+// 查找列表中内容相同或相似的文件. 同一路径多次出现属于正常的分段处理, 不算重复
+pub fn find(paths: &[String], settings: &DuplicateSettings) -> Vec<Group> {
+    let mut unique: Vec<&String> = Vec::new();
+    for path in paths {
+        if !unique.contains(&path) {
+            unique.push(path);
+        }
+    }
+    let fingerprints: Vec<(&String, Fingerprint)> = unique
+        .into_iter()
+        .filter_map(|p| fingerprint(p, settings.perceptual).map(|f| (p, f)))
+        .collect();
+
+    // 内容相同: 按大小和首尾哈希分组, 保持列表中的顺序
+    let mut groups: Vec<Group> = Vec::new();
+    let mut by_content: HashMap<(u64, u64), usize> = HashMap::new();
+    for (path, fingerprint) in &fingerprints {
+        let Some(hash) = fingerprint.hash else {
+            continue;
+        };
+        let key = (fingerprint.signature.0, hash);
+        match by_content.get(&key) {
+            Some(&g) => groups[g].paths.push(path.to_string()),
+            None => {
+                by_content.insert(key, groups.len());
+                groups.push(Group {
+                    kind: MatchKind::Identical,
+                    paths: vec![path.to_string()],
+                });
+            }
+        }
+    }
+    groups.retain(|g| g.paths.len() > 1);
+    if !settings.perceptual {
+        return groups;
+    }
+
+    // 画面相似: 每组相同文件只取第一个参与比较
+    let grouped: Vec<&String> = groups.iter().flat_map(|g| g.paths.iter().skip(1)).collect();
+    let candidates: Vec<(&String, &Vec<u64>)> = fingerprints
+        .iter()
+        .filter(|(p, _)| !grouped.contains(p))
+        .filter_map(|(p, f)| Some((*p, f.frames.as_ref()?.as_ref()?)))
+        .collect();
+    let mut similar: Vec<Group> = Vec::new();
+    let mut assigned: HashMap<&String, usize> = HashMap::new();
+    for (i, (a, frames_a)) in candidates.iter().enumerate() {
+        for (b, frames_b) in &candidates[i + 1..] {
+            if assigned.contains_key(b)
+                || !similar_duration(a, b)
+                || frame_distance(frames_a, frames_b) > settings.threshold
+            {
+                continue;
+            }
+            let g = *assigned.entry(a).or_insert_with(|| {
+                similar.push(Group {
+                    kind: MatchKind::Similar,
+                    paths: vec![a.to_string()],
+                });
+                similar.len() - 1
+            });
+            similar[g].paths.push(b.to_string());
+            assigned.insert(b, g);
+        }
+    }
+    groups.extend(similar);
+    groups
+}
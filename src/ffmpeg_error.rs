@@ -0,0 +1,204 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::i18n::t;
+
+// 根据 ffmpeg 输出识别出的常见失败原因
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum FfmpegErrorKind {
+    UnknownEncoder(String),
+    InvalidData,
+    PermissionDenied,
+    CodecNotSupportedInContainer,
+    NoSpaceLeft,
+    FileNotFound,
+    InvalidOption(String),
+    Unknown,
+}
+
+// 单个任务失败的原因
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TaskError {
+    // 启动 ffmpeg 之前的错误, 如创建目录失败、参数无效
+    Setup(String),
+    // ffmpeg 以非零退出码结束
+    Ffmpeg {
+        kind: FfmpegErrorKind,
+        exit_code: Option<i32>,
+        detail: String, // 匹配到的 ffmpeg 输出行
+    },
+    // ffmpeg 成功退出, 但输出文件未通过校验
+    Verification(String),
+    // 用户停止了处理
+    Cancelled,
+}
+
+impl From<String> for TaskError {
+    fn from(message: String) -> Self {
+        TaskError::Setup(message)
+    }
+}
+
+impl fmt::Display for TaskError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskError::Setup(message) => write!(f, "{}", message),
+            TaskError::Verification(reason) => write!(f, "{}", t!("error.verification", reason)),
+            TaskError::Cancelled => write!(f, "{}", t!("error.cancelled")),
+            TaskError::Ffmpeg {
+                kind, exit_code, ..
+            } => {
+                let code = exit_code.map_or(t!("error.no_code").to_string(), |c| c.to_string());
+                write!(f, "{}", t!("error.with_code", kind.title(), code))
+            }
+        }
+    }
+}
+
+impl TaskError {
+    pub fn explanation(&self) -> String {
+        match self {
+            TaskError::Setup(message) => message.clone(),
+            TaskError::Ffmpeg { kind, .. } => kind.explanation(),
+            TaskError::Verification(_) => t!("error.verification.explanation").to_string(),
+            TaskError::Cancelled => t!("error.cancelled.explanation").to_string(),
+        }
+    }
+
+    pub fn suggestion(&self) -> &'static str {
+        match self {
+            TaskError::Setup(_) => t!("error.setup.suggestion"),
+            TaskError::Ffmpeg { kind, .. } => kind.suggestion(),
+            TaskError::Verification(_) => t!("error.verification.suggestion"),
+            TaskError::Cancelled => t!("error.cancelled.suggestion"),
+        }
+    }
+
+    pub fn detail(&self) -> &str {
+        match self {
+            TaskError::Setup(_) | TaskError::Verification(_) | TaskError::Cancelled => "",
+            TaskError::Ffmpeg { detail, .. } => detail,
+        }
+    }
+}
+
+impl FfmpegErrorKind {
+    // 标题、说明和建议的文本键
+    fn keys(&self) -> (&'static str, &'static str, &'static str) {
+        match self {
+            FfmpegErrorKind::UnknownEncoder(_) => (
+                "ffmpeg.unknown_encoder",
+                "ffmpeg.unknown_encoder.explanation",
+                "ffmpeg.unknown_encoder.suggestion",
+            ),
+            FfmpegErrorKind::InvalidData => (
+                "ffmpeg.invalid_data",
+                "ffmpeg.invalid_data.explanation",
+                "ffmpeg.invalid_data.suggestion",
+            ),
+            FfmpegErrorKind::PermissionDenied => (
+                "ffmpeg.permission_denied",
+                "ffmpeg.permission_denied.explanation",
+                "ffmpeg.permission_denied.suggestion",
+            ),
+            FfmpegErrorKind::CodecNotSupportedInContainer => (
+                "ffmpeg.codec_container",
+                "ffmpeg.codec_container.explanation",
+                "ffmpeg.codec_container.suggestion",
+            ),
+            FfmpegErrorKind::NoSpaceLeft => (
+                "ffmpeg.no_space",
+                "ffmpeg.no_space.explanation",
+                "ffmpeg.no_space.suggestion",
+            ),
+            FfmpegErrorKind::FileNotFound => (
+                "ffmpeg.not_found",
+                "ffmpeg.not_found.explanation",
+                "ffmpeg.not_found.suggestion",
+            ),
+            FfmpegErrorKind::InvalidOption(_) => (
+                "ffmpeg.invalid_option",
+                "ffmpeg.invalid_option.explanation",
+                "ffmpeg.invalid_option.suggestion",
+            ),
+            FfmpegErrorKind::Unknown => (
+                "ffmpeg.unknown",
+                "ffmpeg.unknown.explanation",
+                "ffmpeg.unknown.suggestion",
+            ),
+        }
+    }
+
+    // 编码器或参数名, 填入文本中的 {0}
+    fn name(&self) -> &str {
+        match self {
+            FfmpegErrorKind::UnknownEncoder(name) | FfmpegErrorKind::InvalidOption(name) => name,
+            _ => "",
+        }
+    }
+
+    pub fn title(&self) -> String {
+        t!(self.keys().0, self.name())
+    }
+
+    pub fn explanation(&self) -> String {
+        t!(self.keys().1, self.name())
+    }
+
+    pub fn suggestion(&self) -> &'static str {
+        t!(self.keys().2)
+    }
+}
+
+// 按优先级匹配 ffmpeg 输出中的失败特征
+pub fn classify(lines: &[String]) -> (FfmpegErrorKind, String) {
+    let find = |patterns: &[&str]| {
+        lines
+            .iter()
+            .find(|line| patterns.iter().any(|p| line.contains(p)))
+            .cloned()
+    };
+
+    if let Some(line) = find(&["Unknown encoder", "Encoder not found"]) {
+        return (FfmpegErrorKind::UnknownEncoder(quoted_name(&line)), line);
+    }
+    if let Some(line) = find(&["No space left on device"]) {
+        return (FfmpegErrorKind::NoSpaceLeft, line);
+    }
+    if let Some(line) = find(&["Permission denied"]) {
+        return (FfmpegErrorKind::PermissionDenied, line);
+    }
+    if let Some(line) = find(&[
+        "Could not find tag for codec",
+        "not currently supported in container",
+        "codec not currently supported",
+    ]) {
+        return (FfmpegErrorKind::CodecNotSupportedInContainer, line);
+    }
+    if let Some(line) = find(&["Unrecognized option", "Option not found"]) {
+        return (FfmpegErrorKind::InvalidOption(quoted_name(&line)), line);
+    }
+    if let Some(line) = find(&[
+        "Invalid data found when processing input",
+        "moov atom not found",
+    ]) {
+        return (FfmpegErrorKind::InvalidData, line);
+    }
+    if let Some(line) = find(&["No such file or directory"]) {
+        return (FfmpegErrorKind::FileNotFound, line);
+    }
+
+    // 未识别时取最后一行非进度输出作为参考
+    let detail = lines
+        .iter()
+        .rev()
+        .find(|line| !line.contains("time="))
+        .cloned()
+        .unwrap_or_default();
+    (FfmpegErrorKind::Unknown, detail)
+}
+
+// 提取行中第一个单引号包围的名称, 如 Unknown encoder 'libfoo'
+fn quoted_name(line: &str) -> String {
+    line.split('\'').nth(1).unwrap_or("").to_string()
+}
@@ -0,0 +1,249 @@
+use eframe::egui;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::batch::TaskStatus;
+use crate::i18n::t;
+use crate::media::{self, MediaInfo};
+
+// 文件列表可排序的列
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SortColumn {
+    Name,
+    Duration,
+    Resolution,
+    Codec,
+    Size,
+    Status,
+}
+
+impl SortColumn {
+    pub const ALL: [SortColumn; 6] = [
+        SortColumn::Name,
+        SortColumn::Duration,
+        SortColumn::Resolution,
+        SortColumn::Codec,
+        SortColumn::Size,
+        SortColumn::Status,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SortColumn::Name => t!("files.name"),
+            SortColumn::Duration => t!("files.duration"),
+            SortColumn::Resolution => t!("files.resolution"),
+            SortColumn::Codec => t!("files.codec"),
+            SortColumn::Size => t!("files.size"),
+            SortColumn::Status => t!("files.status"),
+        }
+    }
+}
+
+// 列表中一行显示的信息
+#[derive(Clone)]
+pub struct Row {
+    pub name: String,
+    pub info: MediaInfo,
+    pub size: Option<u64>,
+    pub status: Option<TaskStatus>, // 最近一次处理的结果
+}
+
+impl Row {
+    // info 在后台读取完成前为空
+    pub fn new(path: &str, info: MediaInfo) -> Row {
+        let name = Path::new(path)
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.to_string());
+        Row {
+            name,
+            info,
+            size: fs::metadata(path).ok().map(|m| m.len()),
+            status: None,
+        }
+    }
+
+    // 文件已被移动或删除
+    pub fn missing(&self) -> bool {
+        self.size.is_none()
+    }
+
+    pub fn duration_text(&self) -> String {
+        self.info
+            .duration
+            .map(crate::format_duration)
+            .unwrap_or_default()
+    }
+
+    pub fn resolution_text(&self) -> String {
+        match (self.info.width, self.info.height) {
+            (Some(w), Some(h)) => format!("{}x{}", w, h),
+            _ => String::new(),
+        }
+    }
+
+    pub fn codec_text(&self) -> String {
+        [&self.info.video_codec, &self.info.audio_codec]
+            .into_iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+
+    pub fn size_text(&self) -> String {
+        self.size
+            .map(|size| format!("{:.2} MB", size as f64 / (1024.0 * 1024.0)))
+            .unwrap_or_default()
+    }
+
+    pub fn status_text(&self) -> &'static str {
+        match self.status {
+            Some(status) => status.label(),
+            None if self.missing() => t!("files.missing"),
+            None => "",
+        }
+    }
+
+    // 状态列按处理进度排序, 没有结果的排在最前
+    fn status_rank(&self) -> usize {
+        self.status.map_or(0, |status| status as usize + 1)
+    }
+
+    fn pixels(&self) -> Option<u64> {
+        Some(self.info.width? * self.info.height?)
+    }
+}
+
+// 按列比较两行, 缺少信息的排在最后
+pub fn compare(a: &Row, b: &Row, column: SortColumn) -> Ordering {
+    fn optional<T: PartialOrd>(a: Option<T>, b: Option<T>) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+    match column {
+        SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        SortColumn::Duration => optional(a.info.duration, b.info.duration),
+        SortColumn::Resolution => optional(a.pixels(), b.pixels()),
+        SortColumn::Codec => a.codec_text().cmp(&b.codec_text()),
+        SortColumn::Size => optional(a.size, b.size),
+        SortColumn::Status => a.status_rank().cmp(&b.status_rank()),
+    }
+}
+
+// 移动列表项后, 原下标 index 所在项的新下标
+pub fn moved_index(index: usize, from: usize, to: usize) -> usize {
+    if index == from {
+        to
+    } else if from < index && index <= to {
+        index - 1
+    } else if to <= index && index < from {
+        index + 1
+    } else {
+        index
+    }
+}
+
+// 文件表格各行的缓存. 每帧读取文件信息会卡住界面, 只在列表或处理结果变化时更新
+#[derive(Default)]
+pub struct RowCache {
+    rows: Vec<Row>,                   // 与 paths 一一对应
+    paths: Vec<String>,               // 生成 rows 时的文件列表
+    results: Option<Vec<TaskStatus>>, // 上次计算状态时的处理结果, None 表示需要重新计算
+    probing: HashSet<String>,         // 正在后台读取信息的文件
+    probed: Arc<Mutex<Vec<String>>>,  // 后台读取完成的文件
+}
+
+impl RowCache {
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    // 任务列表重新生成后, 下次更新时重新计算状态
+    pub fn invalidate_statuses(&mut self) {
+        self.results = None;
+    }
+
+    // 按当前文件列表更新缓存, 缺少信息的文件在后台读取.
+    // 返回 true 时需要用 set_statuses 重新设置各行的状态
+    pub fn update(&mut self, ctx: &egui::Context, paths: &[&str], results: &[TaskStatus]) -> bool {
+        let probed = std::mem::take(&mut *self.probed.lock().unwrap());
+        for path in probed {
+            self.probing.remove(&path);
+            let info = media::info(&path);
+            for (row, _) in self
+                .rows
+                .iter_mut()
+                .zip(&self.paths)
+                .filter(|(_, p)| **p == path)
+            {
+                row.info = info.clone();
+            }
+        }
+
+        let list_changed = !self
+            .paths
+            .iter()
+            .map(String::as_str)
+            .eq(paths.iter().copied());
+        if list_changed {
+            let old: HashMap<&str, &Row> = self
+                .paths
+                .iter()
+                .map(String::as_str)
+                .zip(&self.rows)
+                .collect();
+            let mut missing = Vec::new();
+            let rows: Vec<Row> = paths
+                .iter()
+                .map(|&path| {
+                    if let Some(row) = old.get(path) {
+                        return (*row).clone();
+                    }
+                    let info = media::cached_info(path);
+                    if info.is_none() && self.probing.insert(path.to_string()) {
+                        missing.push(path.to_string());
+                    }
+                    Row::new(path, info.unwrap_or_default())
+                })
+                .collect();
+            self.rows = rows;
+            self.paths = paths.iter().map(|p| p.to_string()).collect();
+            if !missing.is_empty() {
+                self.probe(ctx, missing);
+            }
+        }
+
+        let results_changed = self.results.as_deref() != Some(results);
+        if results_changed {
+            self.results = Some(results.to_vec());
+        }
+        list_changed || results_changed
+    }
+
+    pub fn set_statuses(&mut self, statuses: Vec<Option<TaskStatus>>) {
+        for (row, status) in self.rows.iter_mut().zip(statuses) {
+            row.status = status;
+        }
+    }
+
+    // 与添加文件时一样在后台调用 ffprobe, 每读完一个就刷新界面
+    fn probe(&self, ctx: &egui::Context, paths: Vec<String>) {
+        let probed = self.probed.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            for path in paths {
+                media::info(&path);
+                probed.lock().unwrap().push(path);
+                ctx.request_repaint();
+            }
+        });
+    }
+}
@@ -0,0 +1,231 @@
+use eframe::egui::{self, FontData, FontDefinitions, FontFamily, FontId, TextStyle};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::i18n::t;
+use crate::logging;
+
+pub const MIN_FONT_SIZE: f32 = 10.0;
+pub const MAX_FONT_SIZE: f32 = 28.0;
+
+// 界面字体设置
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FontSettings {
+    pub path: String, // 为空时自动查找系统中的中文字体
+    pub size: f32,
+}
+
+impl Default for FontSettings {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            size: 14.0,
+        }
+    }
+}
+
+// 常见的中文字体文件, 按优先级排列
+const CJK_FONT_FILES: &[&str] = &[
+    "notosanscjk-regular.ttc",
+    "notosanscjksc-regular.otf",
+    "notosanssc-regular.otf",
+    "notosanssc-regular.ttf",
+    "sourcehansanssc-regular.otf",
+    "sourcehansans-regular.ttc",
+    "wqy-microhei.ttc",
+    "wqy-zenhei.ttc",
+    "droidsansfallbackfull.ttf",
+    "droidsansfallback.ttf",
+    "msyh.ttc",
+    "simhei.ttf",
+    "simsun.ttc",
+    "pingfang.ttc",
+    "hiragino sans gb.ttc",
+    "stheiti medium.ttc",
+];
+
+const FONT_EXTENSIONS: &[&str] = &["ttf", "otf", "ttc"];
+
+// 子目录最多向下查找的层数
+const MAX_SCAN_DEPTH: usize = 4;
+
+// fontconfig 和 XDG 约定的字体目录, 以及 macOS / Windows 的系统字体目录
+fn font_dirs() -> Vec<PathBuf> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let data_home = std::env::var("XDG_DATA_HOME")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| format!("{}/.local/share", home));
+    let data_dirs = std::env::var("XDG_DATA_DIRS")
+        .ok()
+        .filter(|d| !d.is_empty())
+        .unwrap_or_else(|| "/usr/local/share:/usr/share".to_string());
+
+    let mut dirs = vec![PathBuf::from("fonts")];
+    dirs.push(Path::new(&data_home).join("fonts"));
+    dirs.push(Path::new(&home).join(".fonts"));
+    dirs.extend(data_dirs.split(':').map(|d| Path::new(d).join("fonts")));
+    dirs.extend(
+        [
+            "/usr/share/fonts",
+            "/usr/local/share/fonts",
+            "/System/Library/Fonts",
+            "/Library/Fonts",
+            "C:\\Windows\\Fonts",
+        ]
+        .iter()
+        .map(PathBuf::from),
+    );
+    dirs.dedup();
+    dirs
+}
+
+fn is_font_file(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| FONT_EXTENSIONS.contains(&e.as_str()))
+}
+
+fn scan_dir(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if depth < MAX_SCAN_DEPTH {
+                scan_dir(&path, depth + 1, found);
+            }
+        } else if is_font_file(&path) {
+            found.push(path);
+        }
+    }
+}
+
+// 优先级: 已知中文字体列表中的顺序, 不在列表中的排在最后
+fn rank(path: &Path) -> usize {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    CJK_FONT_FILES
+        .iter()
+        .position(|f| *f == name)
+        .unwrap_or(CJK_FONT_FILES.len())
+}
+
+// 通过 fontconfig 列出支持简体中文的字体
+fn fontconfig_fonts() -> Vec<PathBuf> {
+    let Ok(output) = Command::new("fc-list")
+        .args([":lang=zh-cn", "-f", "%{file}\\n"])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    else {
+        return Vec::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(PathBuf::from)
+        .filter(|p| is_font_file(p))
+        .collect()
+}
+
+// 查找系统中的中文字体. fontconfig 可用时以它为准, 否则按文件名在字体目录中查找
+pub fn find_cjk_font() -> Option<PathBuf> {
+    let mut candidates = fontconfig_fonts();
+    if candidates.is_empty() {
+        let mut found = Vec::new();
+        for dir in font_dirs() {
+            scan_dir(&dir, 0, &mut found);
+        }
+        candidates = found
+            .into_iter()
+            .filter(|p| rank(p) < CJK_FONT_FILES.len())
+            .collect();
+    }
+    candidates.sort_by_key(|p| rank(p));
+    candidates.into_iter().next()
+}
+
+// 读取并检查字体文件, 无法解析的字体会让 egui 在渲染时 panic
+fn read_font(path: &Path) -> Result<Vec<u8>, String> {
+    let bytes = std::fs::read(path).map_err(|e| t!("font.read_failed", path.display(), e))?;
+    ab_glyph::FontRef::try_from_slice(&bytes).map_err(|_| t!("font.invalid", path.display()))?;
+    Ok(bytes)
+}
+
+// 加载界面字体, 返回实际使用的字体文件; 都不可用时使用 egui 自带字体并返回 None
+pub fn load_fonts(ctx: &egui::Context, settings: &FontSettings) -> Option<PathBuf> {
+    let mut fonts = FontDefinitions::default();
+
+    let mut loaded = None;
+    if !settings.path.is_empty() {
+        let path = PathBuf::from(&settings.path);
+        match read_font(&path) {
+            Ok(bytes) => loaded = Some((path, bytes)),
+            Err(e) => logging::log(e),
+        }
+    }
+    if loaded.is_none()
+        && let Some(path) = find_cjk_font()
+    {
+        match read_font(&path) {
+            Ok(bytes) => loaded = Some((path, bytes)),
+            Err(e) => logging::log(e),
+        }
+    }
+
+    let Some((path, bytes)) = loaded else {
+        logging::log(t!("font.not_found"));
+        ctx.set_fonts(fonts);
+        return None;
+    };
+
+    logging::log(t!("font.loaded", path.display()));
+    fonts
+        .font_data
+        .insert("cjk".to_owned(), FontData::from_owned(bytes));
+    // 中文字体放在比例字体首位, 等宽字体中作为后备
+    fonts
+        .families
+        .entry(FontFamily::Proportional)
+        .or_default()
+        .insert(0, "cjk".to_owned());
+    fonts
+        .families
+        .entry(FontFamily::Monospace)
+        .or_default()
+        .push("cjk".to_owned());
+    ctx.set_fonts(fonts);
+    Some(path)
+}
+
+// 按基准字号设置各类文字的大小
+pub fn apply_font_size(ctx: &egui::Context, size: f32) {
+    let size = size.clamp(MIN_FONT_SIZE, MAX_FONT_SIZE);
+    let mut style = (*ctx.style()).clone();
+    style.text_styles = [
+        (
+            TextStyle::Heading,
+            FontId::new(size + 6.0, FontFamily::Proportional),
+        ),
+        (TextStyle::Body, FontId::new(size, FontFamily::Proportional)),
+        (
+            TextStyle::Button,
+            FontId::new(size, FontFamily::Proportional),
+        ),
+        (
+            TextStyle::Monospace,
+            FontId::new(size, FontFamily::Monospace),
+        ),
+        (
+            TextStyle::Small,
+            FontId::new(size * 0.75, FontFamily::Proportional),
+        ),
+    ]
+    .into();
+    ctx.set_style(style);
+}
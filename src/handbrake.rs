@@ -0,0 +1,184 @@
+use serde_json::{Map, Value};
+
+use crate::i18n::t;
+use crate::preset::{self, EncodingSettings, Preset};
+
+// 导入结果: 转换后的预设以及无法映射的字段
+pub struct ImportedPreset {
+    pub preset: Preset,
+    pub unmapped: Vec<String>, // 属于支持范围但取值无法转换的字段
+    pub ignored: Vec<String>,  // 本工具不支持的其他字段
+}
+
+// 已处理的字段以及不影响编码结果的描述性字段
+const HANDLED_KEYS: &[&str] = &[
+    "PresetName",
+    "PresetDescription",
+    "Type",
+    "Default",
+    "Folder",
+    "ChildrenArray",
+    "VideoEncoder",
+    "VideoQualityType",
+    "VideoQualitySlider",
+    "PictureWidth",
+    "PictureHeight",
+    "AudioList",
+    "FileFormat",
+];
+
+// 解析 HandBrake 导出的 JSON 预设文件, 支持预设文件夹(ChildrenArray)
+pub fn import_str(json: &str) -> Result<Vec<ImportedPreset>, String> {
+    let root: Value = serde_json::from_str(json).map_err(|e| t!("handbrake.parse_failed", e))?;
+    let list = root
+        .get("PresetList")
+        .and_then(Value::as_array)
+        .ok_or(t!("handbrake.not_handbrake"))?;
+
+    let mut imported = Vec::new();
+    collect_presets(list, &mut imported);
+    if imported.is_empty() {
+        return Err(t!("handbrake.empty").to_string());
+    }
+    Ok(imported)
+}
+
+fn collect_presets(list: &[Value], imported: &mut Vec<ImportedPreset>) {
+    for item in list {
+        let Some(obj) = item.as_object() else {
+            continue;
+        };
+        if let Some(children) = obj.get("ChildrenArray").and_then(Value::as_array) {
+            collect_presets(children, imported);
+        } else if obj.get("Folder").and_then(Value::as_bool) != Some(true) {
+            imported.push(convert_preset(obj));
+        }
+    }
+}
+
+fn convert_preset(obj: &Map<String, Value>) -> ImportedPreset {
+    let mut unmapped = Vec::new();
+    let mut enc = EncodingSettings::default();
+
+    // 视频编码器
+    if let Some(encoder) = obj.get("VideoEncoder").and_then(Value::as_str) {
+        match map_video_encoder(encoder) {
+            Some(codec) => enc.video_codec = codec.to_string(),
+            None => unmapped.push(t!("handbrake.video_encoder", encoder)),
+        }
+    }
+
+    // 画质: 仅支持恒定质量(VideoQualityType = 2)
+    if enc.video_codec != "copy" {
+        match obj.get("VideoQualityType").and_then(Value::as_u64) {
+            Some(2) | None => {
+                if let Some(q) = obj.get("VideoQualitySlider").and_then(Value::as_f64) {
+                    let max = preset::max_crf(&enc.video_codec);
+                    let crf = q.round().clamp(0.0, max as f64) as u32;
+                    if crf as f64 != q.round() {
+                        unmapped.push(t!("handbrake.quality_range", q, max, crf));
+                    }
+                    enc.crf = Some(crf);
+                }
+            }
+            Some(t) => unmapped.push(t!("handbrake.quality_type", t)),
+        }
+    }
+
+    // 分辨率, 0 表示保持原始尺寸
+    enc.width = positive_u32(obj.get("PictureWidth"));
+    enc.height = positive_u32(obj.get("PictureHeight"));
+
+    // 音频: 只取第一条音轨
+    if let Some(tracks) = obj.get("AudioList").and_then(Value::as_array) {
+        if let Some(track) = tracks.first() {
+            if let Some(encoder) = track.get("AudioEncoder").and_then(Value::as_str) {
+                match map_audio_encoder(encoder) {
+                    Some(codec) => enc.audio_codec = codec.to_string(),
+                    None => unmapped.push(t!("handbrake.audio_encoder", encoder)),
+                }
+            }
+            if enc.audio_codec != "copy" {
+                enc.audio_bitrate = positive_u32(track.get("AudioBitrate"));
+            }
+        }
+        if tracks.len() > 1 {
+            unmapped.push(t!("handbrake.extra_tracks", tracks.len() - 1));
+        }
+    }
+
+    // 输出容器, 如 av_mp4
+    if let Some(format) = obj.get("FileFormat").and_then(Value::as_str) {
+        match map_file_format(format) {
+            Some(container) => enc.container = container.to_string(),
+            None => unmapped.push(t!("handbrake.file_format", format)),
+        }
+    }
+
+    let ignored = obj
+        .keys()
+        .filter(|k| !HANDLED_KEYS.contains(&k.as_str()))
+        .cloned()
+        .collect();
+
+    let name = obj
+        .get("PresetName")
+        .and_then(Value::as_str)
+        .unwrap_or("HandBrake")
+        .to_string();
+
+    ImportedPreset {
+        preset: Preset {
+            name,
+            encoding: enc,
+            ..Preset::default()
+        },
+        unmapped,
+        ignored,
+    }
+}
+
+fn positive_u32(value: Option<&Value>) -> Option<u32> {
+    value
+        .and_then(Value::as_u64)
+        .filter(|&v| v > 0 && v <= u32::MAX as u64)
+        .map(|v| v as u32)
+}
+
+// 只映射到界面中可以选择的编码器(VIDEO_CODECS), 其余作为不支持的字段报告
+fn map_video_encoder(encoder: &str) -> Option<&'static str> {
+    let codec = match encoder {
+        "x264" | "x264_10bit" => "libx264",
+        "x265" | "x265_10bit" | "x265_12bit" => "libx265",
+        "vp9" | "vp9_10bit" => "libvpx-vp9",
+        "svt_av1" | "svt_av1_10bit" => "libsvtav1",
+        _ => return None,
+    };
+    Some(codec)
+}
+
+// 同样只映射到 AUDIO_CODECS 中的编码器
+fn map_audio_encoder(encoder: &str) -> Option<&'static str> {
+    // 直通模式, 如 "copy"、"copy:aac"
+    if encoder == "copy" || encoder.starts_with("copy:") {
+        return Some("copy");
+    }
+    let codec = match encoder {
+        "av_aac" | "ca_aac" | "fdk_aac" | "ca_haac" | "fdk_haac" => "aac",
+        "opus" => "libopus",
+        "mp3" => "libmp3lame",
+        "flac16" | "flac24" => "flac",
+        _ => return None,
+    };
+    Some(codec)
+}
+
+fn map_file_format(format: &str) -> Option<&'static str> {
+    let container = match format {
+        "av_mp4" | "mp4" => "mp4",
+        "av_mkv" | "mkv" => "mkv",
+        "av_webm" | "webm" => "webm",
+        _ => return None,
+    };
+    Some(container)
+}
@@ -0,0 +1,1300 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// 界面语言
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Language {
+    #[serde(rename = "zh-CN")]
+    ZhCn,
+    #[serde(rename = "en-US")]
+    EnUs,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::ZhCn, Language::EnUs];
+
+    // 语言名称始终用该语言本身显示
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::ZhCn => "简体中文",
+            Language::EnUs => "English",
+        }
+    }
+
+    // 配置中没有保存语言时按系统区域设置选择
+    pub fn detect() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        if locale.starts_with("zh") {
+            Language::ZhCn
+        } else {
+            Language::EnUs
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+pub fn set_language(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|&l| l == language)
+        .unwrap_or(0);
+    CURRENT.store(index as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    Language::ALL[CURRENT.load(Ordering::Relaxed) as usize]
+}
+
+fn catalog(language: Language) -> &'static HashMap<&'static str, &'static str> {
+    static ZH: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+    static EN: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+    match language {
+        Language::ZhCn => ZH.get_or_init(|| ZH_CN.iter().copied().collect()),
+        Language::EnUs => EN.get_or_init(|| EN_US.iter().copied().collect()),
+    }
+}
+
+// 查找当前语言的文本, 缺失时使用英文, 仍缺失时返回键名
+pub fn tr(key: &'static str) -> &'static str {
+    catalog(language())
+        .get(key)
+        .or_else(|| catalog(Language::EnUs).get(key))
+        .copied()
+        .unwrap_or(key)
+}
+
+// 带参数的文本, 按位置替换 {0} {1} ..., 译文可以调整参数顺序
+// 只扫描一遍译文, 参数中出现的 {1} 等文字不会被再次替换
+pub fn trf(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut rest = tr(key);
+    let mut text = String::with_capacity(rest.len());
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let arg = after.find('}').and_then(|end| {
+            let index: usize = after[..end].parse().ok()?;
+            Some((args.get(index)?, end))
+        });
+        match arg {
+            Some((arg, end)) => {
+                text.push_str(&arg.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                text.push('{');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+// t!("key") 返回 &'static str, t!("key", a, b) 返回替换参数后的 String
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::trf($key, &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
+pub(crate) use t;
+
+const EN_US: &[(&str, &str)] = &[
+    // 通用
+    ("common.error", "Error: {0}"),
+    ("common.warning", "Warning: {0}"),
+    ("common.suggestion", "Suggestion: {0}"),
+    ("common.cancel", "Cancel"),
+    ("common.refresh", "Refresh"),
+    ("common.browse", "Browse..."),
+    ("common.reset", "Reset"),
+    ("common.remove", "Remove"),
+    // 任务状态
+    ("status.pending", "Pending"),
+    ("status.running", "Running"),
+    ("status.done", "Done"),
+    ("status.suspicious", "Suspicious"),
+    ("status.failed", "Failed"),
+    ("status.skipped", "Skipped"),
+    ("status.cancelled", "Cancelled"),
+    // 批处理
+    ("batch.output_exists", "Output file already exists: {0}"),
+    ("batch.processing", "Processing: {0}"),
+    ("batch.verifying", "Verifying: {0}"),
+    ("batch.summary", "Finished: {0}"),
+    // 命令行
+    (
+        "cli.usage",
+        "Usage:
+  ffmpeg-gui                               start the graphical interface
+  ffmpeg-gui --list-presets                list saved presets
+  ffmpeg-gui [options] <files>...          process files without the interface
+  ffmpeg-gui --watch [options] [dir]       watch a folder and process new files
+
+Options:
+  --preset <name>       use a saved preset
+  --start <time>        start time (HH:MM:SS)
+  --end <time>          end time (HH:MM:SS)
+  --speed <factor>      playback speed, e.g. 2 or 0.5
+  --reverse             play the clip backwards
+  --drop-audio          remove the audio track
+  --output-dir <dir>    output directory, overrides the preset
+  --report <file>       export a report when finished (.csv or .json)
+  --on-conflict <mode>  when the output exists: overwrite, skip or number
+  -h, --help            show this help",
+    ),
+    ("cli.missing_value", "Option {0} requires a value"),
+    ("cli.unknown_conflict", "Unknown conflict policy: {0}"),
+    ("cli.unknown_option", "Unknown option: {0}"),
+    ("cli.invalid_speed", "Invalid speed: {0}"),
+    ("cli.no_input", "No input files given"),
+    ("cli.preset_not_found", "Preset not found: {0}"),
+    ("cli.invalid_template", "Invalid filename template: {0}"),
+    ("cli.conflicts", "Output conflicts (policy: {0}):"),
+    // 输出冲突
+    ("collision.ask", "Ask"),
+    ("collision.overwrite", "Overwrite"),
+    ("collision.skip", "Skip"),
+    ("collision.counter", "Append number"),
+    ("collision.exists", "{0} (file exists)"),
+    ("collision.duplicate", "{0} (same output as task {1})"),
+    ("collision.renamed", "Output conflict, writing to: {0}"),
+    ("conflict.title", "Output conflicts"),
+    ("conflict.count", "{0} tasks have conflicting outputs:"),
+    (
+        "conflict.note",
+        "Duplicate outputs within the batch are always numbered.",
+    ),
+    // 追加参数
+    (
+        "args.trailing_escape",
+        "Arguments end with an unfinished escape",
+    ),
+    ("args.unclosed_quote", "Unclosed quote {0}"),
+    (
+        "args.managed",
+        "Option {0} is managed by the program and cannot be added",
+    ),
+    ("args.missing_value", "Option {0} is missing a value"),
+    ("args.global_input", "Global input arguments: {0}"),
+    ("args.global_output", "Global output arguments: {0}"),
+    ("args.file_input", "File input arguments: {0}"),
+    ("args.file_output", "File output arguments: {0}"),
+    // 时间输入
+    ("time.start", "Start time"),
+    ("time.end", "End time"),
+    (
+        "time.invalid",
+        "Unrecognised time \"{0}\"; use HH:MM:SS[.mmm], MM:SS, seconds, a frame number such as 120f or timecode HH:MM:SS:FF",
+    ),
+    (
+        "time.field_range",
+        "Minutes and seconds in \"{0}\" must be two digits below 60",
+    ),
+    (
+        "time.no_frame_rate",
+        "Frame numbers need the video frame rate, which could not be read",
+    ),
+    (
+        "time.end_before_start",
+        "The end time must be after the start time",
+    ),
+    (
+        "time.beyond_duration",
+        "{0} {1} is beyond the video length {2}",
+    ),
+    ("time.hint", "0 or empty: no trimming"),
+    ("time.frame_range", "Frame number in {0} must be below {1}"),
+    (
+        "time.drop_frame_unsupported",
+        "Drop-frame timecode only applies to 29.97 or 59.94 fps, the video is {0} fps",
+    ),
+    (
+        "time.dropped_frame",
+        "{0} does not exist in drop-frame timecode",
+    ),
+    (
+        "time.before_start_timecode",
+        "Timecode {0} is before the file's start timecode {1}",
+    ),
+    ("time.timecode_range", "Timecode: {0} - {1}"),
+    ("ui.frame_rate", "Frame rate: {0} fps"),
+    ("ui.start_timecode", "Start timecode: {0}"),
+    // 输出容器
+    (
+        "container.unsupported_video",
+        "{0} does not support video codec {1}{2}",
+    ),
+    (
+        "container.unsupported_audio",
+        "{0} does not support audio codec {1}{2}",
+    ),
+    ("container.copy_note", " (copied from the source)"),
+    (
+        "container.fix_hint",
+        "{0}; re-encode or choose a more general container such as MKV",
+    ),
+    // HandBrake 导入
+    ("handbrake.parse_failed", "Failed to parse JSON: {0}"),
+    (
+        "handbrake.not_handbrake",
+        "Not a HandBrake preset file: PresetList is missing",
+    ),
+    ("handbrake.empty", "The file contains no presets to import"),
+    (
+        "handbrake.video_encoder",
+        "VideoEncoder: unsupported encoder \"{0}\"",
+    ),
+    (
+        "handbrake.quality_type",
+        "VideoQualityType: only constant quality is supported, got {0}",
+    ),
+    (
+        "handbrake.quality_range",
+        "VideoQualitySlider: {0} is outside the CRF range 0-{1}, clamped to {2}",
+    ),
+    (
+        "handbrake.audio_encoder",
+        "AudioList[0].AudioEncoder: unsupported encoder \"{0}\"",
+    ),
+    (
+        "handbrake.extra_tracks",
+        "AudioList: only the first track is imported, {0} more ignored",
+    ),
+    (
+        "handbrake.file_format",
+        "FileFormat: unsupported container \"{0}\"",
+    ),
+    ("handbrake.import", "Import HandBrake presets..."),
+    ("handbrake.filter", "HandBrake presets"),
+    ("handbrake.close_report", "Close import report"),
+    ("handbrake.read_failed", "Failed to read file: {0}"),
+    ("handbrake.import_failed", "Import failed: {0}"),
+    ("handbrake.imported", "Imported preset: {0}"),
+    ("handbrake.unmapped", "    Not mapped: {0}"),
+    (
+        "handbrake.ignored",
+        "    Ignored {0} unsupported fields: {1}",
+    ),
+    // 日志
+    ("log.read_failed", "Cannot read log file {0}: {1}"),
+    ("log.file_missing", "File not found: {0}"),
+    ("log.ffprobe_failed", "Failed to run ffprobe"),
+    (
+        "log.ffprobe_status",
+        "ffprobe {0} exit status: {1}\nstdout:\n{2}\nstderr:\n{3}",
+    ),
+    (
+        "log.preview_failed",
+        "Preview generation failed, exit code: {0}",
+    ),
+    ("log.final_command", "FFmpeg command: {0}"),
+    ("log.create_failed", "Cannot create log file {0}: {1}"),
+    ("log.label", "Log:"),
+    ("log.live", "Current task (live)"),
+    ("log.app", "Application log"),
+    ("log.show", "Show log"),
+    ("log.hide", "Hide log"),
+    // 预设
+    ("trim.accurate", "Accurate trim"),
+    ("trim.fast", "Fast trim (keyframes)"),
+    ("preset.name_empty", "Preset name cannot be empty"),
+    ("preset.name_exists", "Preset \"{0}\" already exists"),
+    ("preset.label", "Preset:"),
+    ("preset.none", "(none)"),
+    ("preset.name", "Name:"),
+    ("preset.new", "New"),
+    ("preset.save", "Save"),
+    ("preset.rename", "Rename"),
+    ("preset.delete", "Delete"),
+    // 项目
+    (
+        "project.serialize_failed",
+        "Failed to serialize project: {0}",
+    ),
+    ("project.write_failed", "Failed to write project file: {0}"),
+    ("project.read_failed", "Failed to read project file: {0}"),
+    ("project.invalid", "Invalid project file: {0}"),
+    (
+        "project.version",
+        "Project file version {0} is newer than the supported version {1}",
+    ),
+    ("project.saved", "Project saved: {0}"),
+    ("project.opened", "Project opened: {0}"),
+    (
+        "project.opened_missing",
+        "Project opened, {0} files are missing",
+    ),
+    ("project.open", "Open project..."),
+    ("project.save", "Save project..."),
+    ("project.filter", "Project files"),
+    // 报告
+    ("report.json_failed", "Failed to generate JSON: {0}"),
+    ("report.write_failed", "Failed to write report: {0}"),
+    ("report.exported", "Report exported: {0}"),
+    ("report.export", "Export report..."),
+    // 文件名清理
+    ("sanitize.replace", "Replace"),
+    ("sanitize.remove", "Remove"),
+    ("whitespace.keep", "Keep"),
+    ("whitespace.underscore", "Replace with underscore"),
+    ("whitespace.remove", "Remove"),
+    ("sanitize.title", "Filename sanitization"),
+    (
+        "sanitize.keep_unicode",
+        "Keep letters of all languages (otherwise only English, digits and Chinese)",
+    ),
+    ("sanitize.invalid_chars", "Unsupported characters:"),
+    ("sanitize.whitespace", "Whitespace:"),
+    ("sanitize.max_length", "Maximum length (0 for unlimited):"),
+    (
+        "sanitize.avoid_reserved",
+        "Avoid Windows reserved names (CON, NUL, ...)",
+    ),
+    ("sanitize.sample", "Sample:"),
+    ("sanitize.result", "Sanitized: {0}"),
+    // 输出校验
+    (
+        "verify.unreadable",
+        "Cannot read the output file, it may be missing or corrupted",
+    ),
+    ("verify.no_video", "The output file has no video stream"),
+    ("verify.no_audio", "The output file has no audio stream"),
+    (
+        "verify.duration_mismatch",
+        "Output duration {0}s differs from the expected {1}s by more than {2}s",
+    ),
+    ("verify.no_duration", "Cannot read the output duration"),
+    (
+        "verify.decode_start_failed",
+        "Failed to start the decode check: {0}",
+    ),
+    ("verify.decode_error", "Decode check found errors: {0}"),
+    ("verify.enabled", "Verify outputs after processing"),
+    ("verify.tolerance", "Duration tolerance (s):"),
+    ("verify.full_decode", "Full decode check (slow)"),
+    // 文件名模板
+    ("var.input_name", "Source file name without extension"),
+    ("var.ext", "Source file extension"),
+    ("var.parent_dir", "Name of the source file's folder"),
+    ("var.rotation", "Rotation angle"),
+    ("var.start", "Start time"),
+    ("var.end", "End time"),
+    ("var.duration", "Output duration"),
+    ("var.width", "Video width"),
+    ("var.height", "Video height"),
+    ("var.codec", "Video codec"),
+    ("var.index", "Position in the batch"),
+    ("var.segment", "Segment number of the same source file"),
+    ("var.timestamp", "Current time, default %Y%m%d%H%M%S"),
+    ("var.date", "Current date, default %Y-%m-%d"),
+    ("var.time", "Current time of day, default %H-%M-%S"),
+    ("var.creation_date", "Media creation date, default %Y-%m-%d"),
+    ("template.extra_brace", "Unexpected \"}\": {0}"),
+    ("template.unclosed", "Variable is missing \"}\": {0}"),
+    ("template.empty", "The filename template cannot be empty"),
+    (
+        "template.relative",
+        "The template may only contain paths inside the output directory",
+    ),
+    ("template.unknown_var", "Unknown variable: {0}"),
+    (
+        "template.no_format",
+        "Variable {0} does not accept a custom format",
+    ),
+    ("template.bad_date_format", "Invalid date format: {0}"),
+    ("template.label", "Filename template:"),
+    ("template.variables", "Available variables"),
+    (
+        "template.help",
+        "Date variables accept a format, e.g. {date:%Y%m}; / in the template creates subfolders",
+    ),
+    ("template.invalid", "Invalid template: {0}"),
+    ("template.preview", "Output preview: {0}"),
+    ("template.error", "Filename template: {0}"),
+    // 错误说明
+    ("error.verification", "Output verification failed: {0}"),
+    ("error.cancelled", "Cancelled"),
+    ("error.no_code", "none"),
+    ("error.with_code", "{0} (exit code: {1})"),
+    (
+        "error.verification.explanation",
+        "ffmpeg reported success, but the output is missing expected streams or cannot be fully decoded.",
+    ),
+    (
+        "error.cancelled.explanation",
+        "Processing was stopped by the user; the output may be incomplete.",
+    ),
+    (
+        "error.setup.suggestion",
+        "Check the output directory and processing settings.",
+    ),
+    (
+        "error.verification.suggestion",
+        "Check the task log, try accurate trimming or re-encoding, and inspect the source if needed.",
+    ),
+    (
+        "error.cancelled.suggestion",
+        "Simply start processing again.",
+    ),
+    ("ffmpeg.unknown_encoder", "Unknown encoder {0}"),
+    ("ffmpeg.invalid_data", "Invalid input data"),
+    ("ffmpeg.permission_denied", "Permission denied"),
+    (
+        "ffmpeg.codec_container",
+        "Codec not supported by the container",
+    ),
+    ("ffmpeg.no_space", "No space left on disk"),
+    ("ffmpeg.not_found", "File or directory not found"),
+    ("ffmpeg.invalid_option", "Invalid option {0}"),
+    ("ffmpeg.unknown", "FFmpeg processing failed"),
+    (
+        "ffmpeg.unknown_encoder.explanation",
+        "The installed ffmpeg does not include the encoder \"{0}\"; the name may be misspelled or it was not enabled at build time.",
+    ),
+    (
+        "ffmpeg.invalid_data.explanation",
+        "ffmpeg cannot parse the input; the file may be corrupted, incomplete or not a media file.",
+    ),
+    (
+        "ffmpeg.permission_denied.explanation",
+        "ffmpeg has no permission to read the input or write the output location.",
+    ),
+    (
+        "ffmpeg.codec_container.explanation",
+        "The selected codecs cannot be written to the output container.",
+    ),
+    (
+        "ffmpeg.no_space.explanation",
+        "The disk filled up while writing the output.",
+    ),
+    (
+        "ffmpeg.not_found.explanation",
+        "The input file or the output directory does not exist.",
+    ),
+    (
+        "ffmpeg.invalid_option.explanation",
+        "ffmpeg does not recognise the option \"{0}\" or its value is invalid.",
+    ),
+    (
+        "ffmpeg.unknown.explanation",
+        "The cause could not be identified; please check the task log.",
+    ),
+    (
+        "ffmpeg.unknown_encoder.suggestion",
+        "Run ffmpeg -encoders to list the available encoders, or use a common one such as copy or libx264.",
+    ),
+    (
+        "ffmpeg.invalid_data.suggestion",
+        "Check that the file plays in a media player, and copy the source again if needed.",
+    ),
+    (
+        "ffmpeg.permission_denied.suggestion",
+        "Check the permissions of the file and output directory, or choose another output directory.",
+    ),
+    (
+        "ffmpeg.codec_container.suggestion",
+        "Use a compatible codec (e.g. aac audio for MP4) or a more general container such as MKV.",
+    ),
+    (
+        "ffmpeg.no_space.suggestion",
+        "Free up disk space or move the output directory to another disk.",
+    ),
+    (
+        "ffmpeg.not_found.suggestion",
+        "Make sure the source file is still in place and check the output path.",
+    ),
+    (
+        "ffmpeg.invalid_option.suggestion",
+        "Check the spelling and values of the extra input/output arguments.",
+    ),
+    (
+        "ffmpeg.unknown.suggestion",
+        "Open the log panel to see the full ffmpeg output.",
+    ),
+    // 界面字体
+    ("font.title", "Interface font"),
+    ("font.path", "Font file:"),
+    (
+        "font.auto_hint",
+        "Leave empty to find a CJK font automatically",
+    ),
+    ("font.filter", "Font files"),
+    ("font.size", "Font size:"),
+    ("font.current", "Current font: {0}"),
+    (
+        "font.default",
+        "No CJK font found, using the built-in egui fonts",
+    ),
+    ("font.loaded", "Loaded font: {0}"),
+    (
+        "font.not_found",
+        "No usable CJK font found, falling back to the egui fonts",
+    ),
+    ("font.read_failed", "Cannot read font {0}: {1}"),
+    ("font.invalid", "Not a valid font file: {0}"),
+    // 处理过程
+    ("process.mkdir_failed", "Failed to create directory: {0}"),
+    ("process.spawn_failed", "Failed to start FFmpeg: {0}"),
+    ("process.no_stderr", "Cannot capture the stderr pipe"),
+    ("process.wait_failed", "Failed to wait for FFmpeg: {0}"),
+    // 界面
+    ("ui.app_title", "Video Processing Tool"),
+    ("ui.language", "Language:"),
+    ("ui.language_system", "Follow system"),
+    (
+        "ui.drop_hint",
+        "Drop files here or use the buttons below to add files",
+    ),
+    ("ui.start_preview", "Start preview (HH:MM:SS):"),
+    ("ui.end_preview", "End preview (HH:MM:SS):"),
+    ("ui.generate_preview", "🔄 Generate preview"),
+    ("ui.start_preview_loading", "Generating start preview..."),
+    ("ui.end_preview_loading", "Generating end preview..."),
+    ("ui.selected_files", "Selected files:"),
+    ("ui.file_count", "{0} files"),
+    ("ui.clear_list", "Clear list"),
+    ("ui.copy_params", "Copy selected parameters"),
+    ("ui.no_file", "No video file selected."),
+    ("ui.video_info", "Video information"),
+    ("ui.video_duration", "Duration: {0}"),
+    ("ui.video_size", "Size: {0}"),
+    ("ui.video_format", "Format: {0}"),
+    ("ui.settings", "Settings"),
+    ("ui.output_dir", "Output directory:"),
+    ("ui.current_file", "Current file: {0}"),
+    (
+        "ui.editing_defaults",
+        "Editing: default parameters (for newly added files)",
+    ),
+    ("ui.apply_all", "Apply to all files"),
+    ("ui.start_time", "Start time:"),
+    ("ui.end_time", "End time:"),
+    ("ui.rotation", "Rotation:"),
+    ("ui.trim_mode", "Trim mode:"),
+    ("ui.extra_input", "Extra input arguments:"),
+    ("ui.extra_output", "Extra output arguments:"),
+    ("ui.global_input", "Global input arguments:"),
+    ("ui.global_output", "Global output arguments:"),
+    ("ui.log_dir", "Log directory:"),
+    ("ui.log_dir_hint", "Leave empty to save next to the output"),
+    ("ui.command_preview", "Command preview"),
+    (
+        "ui.command_hint",
+        "Select a file to see the ffmpeg command that will be run",
+    ),
+    ("ui.copy_command", "Copy command"),
+    ("ui.arg_list", "Arguments"),
+    ("ui.invalid_command", "Invalid command: {0}"),
+    ("ui.start", "Start processing"),
+    ("ui.stop", "Stop"),
+    (
+        "ui.continue_on_error",
+        "Continue with the remaining files after an error",
+    ),
+    ("ui.on_conflict", "When the output exists:"),
+    ("ui.task", "Task {0}"),
+    ("ui.task_item", "Task {0}: {1}"),
+    ("ui.progress", "Progress: {0}%"),
+    ("ui.results", "Task results"),
+    ("enc.video_codec", "Video codec:"),
+    ("enc.width", "Width"),
+    ("enc.height", "Height"),
+    ("enc.video_filters", "Video filters:"),
+    ("enc.filters_hint", "e.g. hflip,eq=contrast=1.1"),
+    ("enc.container", "Output container:"),
+    ("enc.same_container", "Same as source"),
+    ("enc.audio_codec", "Audio codec:"),
+    ("enc.audio_bitrate", "Bitrate (kbps)"),
+    // 快捷键
+    ("shortcut.title", "Keyboard shortcuts"),
+    ("shortcut.mark_in", "Set start time at the playhead"),
+    ("shortcut.mark_out", "Set end time at the playhead"),
+    ("shortcut.step_frame", "Step one frame back / forward"),
+    ("shortcut.step_second", "Step one second back / forward"),
+    ("shortcut.play", "Play / pause"),
+    ("shortcut.start", "Start processing"),
+    ("shortcut.remove", "Remove the selected file"),
+    ("shortcut.help", "Show / hide this help"),
+    (
+        "shortcut.typing_note",
+        "While a text field has focus, only Ctrl + Enter, F1 and Esc are active.",
+    ),
+    ("playhead.title", "Playhead:"),
+    ("playhead.mark_in", "Set start (I)"),
+    ("playhead.mark_out", "Set end (O)"),
+    // 监视文件夹
+    ("watch.title", "Watch folder"),
+    ("watch.input_dir", "Watched folder:"),
+    ("watch.output_dir", "Output folder:"),
+    ("watch.archive_dir", "Archive folder:"),
+    (
+        "watch.dir_hint",
+        "Leave the output folder empty to use the preset's; leave the archive folder empty to keep sources in place.",
+    ),
+    ("watch.preset", "Preset:"),
+    ("watch.default_preset", "Default settings"),
+    (
+        "watch.stable_secs",
+        "Process after the size is unchanged for (s):",
+    ),
+    (
+        "watch.include_existing",
+        "Also process files already in the folder",
+    ),
+    ("watch.start", "Start watching"),
+    ("watch.stop", "Stop watching"),
+    ("watch.input_missing", "Watched folder does not exist: {0}"),
+    (
+        "watch.archive_same",
+        "The archive folder must differ from the watched folder",
+    ),
+    (
+        "watch.output_same",
+        "The output folder must differ from the watched folder",
+    ),
+    ("watch.watching", "Watching {0} for new files"),
+    ("watch.stopped", "Stopped watching {0}"),
+    ("watch.found", "New file ready: {0}"),
+    ("watch.failed", "Processing {0} failed: {1}"),
+    ("watch.archived", "Moved {0} to {1}"),
+    ("watch.archive_failed", "Could not archive {0}: {1}"),
+    // 添加文件
+    ("import.options", "File filter"),
+    ("import.extensions", "Extensions:"),
+    ("import.extensions_hint", "Empty: accept every extension"),
+    ("import.validate", "Check files with ffprobe before adding"),
+    ("import.scanning", "Scanning and checking files..."),
+    ("import.summary", "Added {0} files, skipped {1}"),
+    ("import.skipped_count", "{0} items skipped"),
+    ("import.skipped", "Skipped {0}: {1}"),
+    ("import.dismiss", "Dismiss"),
+    ("import.duplicate", "already in the list"),
+    ("import.extension", "extension not in the filter"),
+    ("import.too_deep", "folder nested too deeply"),
+    ("import.invalid", "not a readable media file: {0}"),
+    ("import.no_streams", "no audio or video streams"),
+    (
+        "import.probe_unavailable",
+        "Cannot run ffprobe, adding files without checking them: {0}",
+    ),
+    // 文件列表
+    ("files.add_files", "Add files..."),
+    ("files.add_folder", "Add folder..."),
+    ("files.remove_checked", "Remove selected"),
+    ("files.checked_count", "({0} selected)"),
+    ("files.media_filter", "Media files"),
+    ("files.name", "Name"),
+    ("files.duration", "Duration"),
+    ("files.resolution", "Resolution"),
+    ("files.codec", "Codec"),
+    ("files.size", "Size"),
+    ("files.status", "Status"),
+    ("files.missing", "Missing"),
+    (
+        "files.drag_hint",
+        "Drag to reorder. Ctrl+click or Shift+click a name to select several files.",
+    ),
+    // 重复文件
+    ("duplicate.check", "Find duplicates"),
+    ("duplicate.perceptual", "Compare frames"),
+    (
+        "duplicate.perceptual_hint",
+        "Also find re-encoded or resized copies by comparing sampled frames. Runs ffmpeg several times per file.",
+    ),
+    ("duplicate.threshold", "Tolerance:"),
+    ("duplicate.scanning", "Comparing files..."),
+    ("duplicate.summary", "{0} files have duplicates"),
+    ("duplicate.select", "Select extra copies"),
+    ("duplicate.identical_to", "Same content as:\n{0}"),
+    ("duplicate.similar_to", "Looks like:\n{0}"),
+    ("duplicate.found", "Duplicates: {0}"),
+    // 变速
+    ("speed.factor", "Speed:"),
+    ("speed.drop_audio", "Remove audio"),
+    ("speed.reverse", "Reverse"),
+    (
+        "speed.reverse_hint",
+        "Reversing buffers the whole clip in memory; trim it to at most {0} seconds.",
+    ),
+    ("speed.factor_range", "Speed {0} is outside {1}-{2}"),
+    (
+        "speed.reverse_too_long",
+        "The clip to reverse is {0} long; reverse at most {1}. Set a shorter start/end range.",
+    ),
+    (
+        "speed.video_copy",
+        "Changing speed or reversing requires re-encoding: choose a video codec other than copy",
+    ),
+    (
+        "speed.audio_copy",
+        "Changing speed or reversing requires re-encoding: choose an audio codec other than copy, or remove audio",
+    ),
+    (
+        "speed.nothing_left",
+        "The output container is audio-only, so removing audio leaves nothing to write",
+    ),
+];
+
+const ZH_CN: &[(&str, &str)] = &[
+    // 通用
+    ("common.error", "错误: {0}"),
+    ("common.warning", "警告: {0}"),
+    ("common.suggestion", "建议: {0}"),
+    ("common.cancel", "取消"),
+    ("common.refresh", "刷新"),
+    ("common.browse", "选择..."),
+    ("common.reset", "重置"),
+    ("common.remove", "移除"),
+    // 任务状态
+    ("status.pending", "等待中"),
+    ("status.running", "处理中"),
+    ("status.done", "完成"),
+    ("status.suspicious", "可疑"),
+    ("status.failed", "失败"),
+    ("status.skipped", "已跳过"),
+    ("status.cancelled", "已取消"),
+    // 批处理
+    ("batch.output_exists", "输出文件已存在: {0}"),
+    ("batch.processing", "处理中: {0}"),
+    ("batch.verifying", "校验中: {0}"),
+    ("batch.summary", "处理完成: {0}"),
+    // 命令行
+    (
+        "cli.usage",
+        "用法:
+  ffmpeg-gui                               启动图形界面
+  ffmpeg-gui --list-presets                列出已保存的预设
+  ffmpeg-gui [选项] <文件>...              不启动界面, 直接处理文件
+  ffmpeg-gui --watch [选项] [目录]         监视文件夹并处理新文件
+
+选项:
+  --preset <名称>       使用已保存的预设
+  --start <时间>        开始时间 (HH:MM:SS)
+  --end <时间>          结束时间 (HH:MM:SS)
+  --speed <倍数>        播放速度, 如 2 或 0.5
+  --reverse             倒放
+  --drop-audio          去掉音频
+  --output-dir <目录>   输出目录, 覆盖预设中的设置
+  --report <文件>       处理结束后导出报告 (.csv 或 .json)
+  --on-conflict <方式>  输出文件已存在时: overwrite 覆盖, skip 跳过, number 自动编号
+  -h, --help            显示帮助",
+    ),
+    ("cli.missing_value", "参数 {0} 缺少取值"),
+    ("cli.unknown_conflict", "未知的冲突处理方式: {0}"),
+    ("cli.unknown_option", "未知参数: {0}"),
+    ("cli.invalid_speed", "无效的速度: {0}"),
+    ("cli.no_input", "未指定输入文件"),
+    ("cli.preset_not_found", "预设不存在: {0}"),
+    ("cli.invalid_template", "文件名模板无效: {0}"),
+    ("cli.conflicts", "输出文件冲突 (处理方式: {0}):"),
+    // 输出冲突
+    ("collision.ask", "每次询问"),
+    ("collision.overwrite", "覆盖"),
+    ("collision.skip", "跳过"),
+    ("collision.counter", "自动编号"),
+    ("collision.exists", "{0} (文件已存在)"),
+    ("collision.duplicate", "{0} (与任务 {1} 的输出相同)"),
+    ("collision.renamed", "输出文件冲突, 改为: {0}"),
+    ("conflict.title", "输出文件冲突"),
+    ("conflict.count", "{0} 个任务的输出文件有冲突:"),
+    ("conflict.note", "同一批次中重复的输出总是自动编号。"),
+    // 追加参数
+    ("args.trailing_escape", "参数以未完成的转义符结尾"),
+    ("args.unclosed_quote", "引号 {0} 未闭合"),
+    ("args.managed", "参数 {0} 由程序管理, 不能手动添加"),
+    ("args.missing_value", "选项 {0} 缺少取值"),
+    ("args.global_input", "全局输入参数: {0}"),
+    ("args.global_output", "全局输出参数: {0}"),
+    ("args.file_input", "文件输入参数: {0}"),
+    ("args.file_output", "文件输出参数: {0}"),
+    // 时间输入
+    ("time.start", "开始时间"),
+    ("time.end", "结束时间"),
+    (
+        "time.invalid",
+        "无法识别的时间 \"{0}\", 可用格式: HH:MM:SS[.mmm]、MM:SS、秒数、帧号(如 120f)或时间码 HH:MM:SS:FF",
+    ),
+    (
+        "time.field_range",
+        "\"{0}\" 中的分钟和秒必须是小于 60 的两位数",
+    ),
+    ("time.no_frame_rate", "使用帧号需要视频帧率, 但无法读取"),
+    ("time.end_before_start", "结束时间必须晚于开始时间"),
+    ("time.beyond_duration", "{0} {1} 超出视频长度 {2}"),
+    ("time.hint", "0 或留空表示不裁剪"),
+    ("time.frame_range", "{0} 中的帧号必须小于 {1}"),
+    (
+        "time.drop_frame_unsupported",
+        "丢帧时间码只适用于 29.97 或 59.94 fps, 当前视频为 {0} fps",
+    ),
+    ("time.dropped_frame", "丢帧时间码中不存在 {0}"),
+    (
+        "time.before_start_timecode",
+        "时间码 {0} 早于文件的起始时间码 {1}",
+    ),
+    ("time.timecode_range", "时间码: {0} - {1}"),
+    ("ui.frame_rate", "帧率: {0} fps"),
+    ("ui.start_timecode", "起始时间码: {0}"),
+    // 输出容器
+    (
+        "container.unsupported_video",
+        "{0} 容器不支持视频编码 {1}{2}",
+    ),
+    (
+        "container.unsupported_audio",
+        "{0} 容器不支持音频编码 {1}{2}",
+    ),
+    ("container.copy_note", " (复制源文件的流)"),
+    (
+        "container.fix_hint",
+        "{0}, 请改为重新编码或选择 MKV 等更通用的容器",
+    ),
+    // HandBrake 导入
+    ("handbrake.parse_failed", "JSON 解析失败: {0}"),
+    (
+        "handbrake.not_handbrake",
+        "不是 HandBrake 预设文件: 缺少 PresetList",
+    ),
+    ("handbrake.empty", "文件中没有可导入的预设"),
+    (
+        "handbrake.video_encoder",
+        "VideoEncoder: 不支持的编码器 \"{0}\"",
+    ),
+    (
+        "handbrake.quality_type",
+        "VideoQualityType: 仅支持恒定质量模式, 当前为 {0}",
+    ),
+    (
+        "handbrake.quality_range",
+        "VideoQualitySlider: {0} 超出 CRF 范围 0-{1}, 已改为 {2}",
+    ),
+    (
+        "handbrake.audio_encoder",
+        "AudioList[0].AudioEncoder: 不支持的编码器 \"{0}\"",
+    ),
+    (
+        "handbrake.extra_tracks",
+        "AudioList: 仅导入第一条音轨, 忽略其余 {0} 条",
+    ),
+    (
+        "handbrake.file_format",
+        "FileFormat: 不支持的输出容器 \"{0}\"",
+    ),
+    ("handbrake.import", "导入 HandBrake 预设..."),
+    ("handbrake.filter", "HandBrake 预设"),
+    ("handbrake.close_report", "关闭导入报告"),
+    ("handbrake.read_failed", "读取文件失败: {0}"),
+    ("handbrake.import_failed", "导入失败: {0}"),
+    ("handbrake.imported", "已导入预设: {0}"),
+    ("handbrake.unmapped", "    未能映射 {0}"),
+    ("handbrake.ignored", "    忽略 {0} 个不支持的字段: {1}"),
+    // 日志
+    ("log.read_failed", "无法读取日志文件 {0}: {1}"),
+    ("log.file_missing", "文件不存在: {0}"),
+    ("log.ffprobe_failed", "执行 ffprobe 失败"),
+    (
+        "log.ffprobe_status",
+        "ffprobe {0} 退出状态: {1}\nstdout:\n{2}\nstderr:\n{3}",
+    ),
+    ("log.preview_failed", "预览生成失败, 退出码: {0}"),
+    ("log.final_command", "最终FFmpeg命令: {0}"),
+    ("log.create_failed", "无法创建日志文件 {0}: {1}"),
+    ("log.label", "日志:"),
+    ("log.live", "当前任务(实时)"),
+    ("log.app", "程序日志"),
+    ("log.show", "显示日志"),
+    ("log.hide", "隐藏日志"),
+    // 预设
+    ("trim.accurate", "精确裁剪"),
+    ("trim.fast", "快速裁剪(关键帧)"),
+    ("preset.name_empty", "预设名称不能为空"),
+    ("preset.name_exists", "预设 \"{0}\" 已存在"),
+    ("preset.label", "预设:"),
+    ("preset.none", "(未选择)"),
+    ("preset.name", "名称:"),
+    ("preset.new", "新建"),
+    ("preset.save", "保存"),
+    ("preset.rename", "重命名"),
+    ("preset.delete", "删除"),
+    // 项目
+    ("project.serialize_failed", "序列化项目失败: {0}"),
+    ("project.write_failed", "写入项目文件失败: {0}"),
+    ("project.read_failed", "读取项目文件失败: {0}"),
+    ("project.invalid", "项目文件格式错误: {0}"),
+    (
+        "project.version",
+        "项目文件版本 {0} 高于当前程序支持的版本 {1}",
+    ),
+    ("project.saved", "项目已保存: {0}"),
+    ("project.opened", "项目已打开: {0}"),
+    (
+        "project.opened_missing",
+        "项目已打开, 其中 {0} 个文件不存在",
+    ),
+    ("project.open", "打开项目..."),
+    ("project.save", "保存项目..."),
+    ("project.filter", "项目文件"),
+    // 报告
+    ("report.json_failed", "生成 JSON 失败: {0}"),
+    ("report.write_failed", "写入报告失败: {0}"),
+    ("report.exported", "报告已导出: {0}"),
+    ("report.export", "导出报告..."),
+    // 文件名清理
+    ("sanitize.replace", "替换"),
+    ("sanitize.remove", "删除"),
+    ("whitespace.keep", "保留"),
+    ("whitespace.underscore", "替换为下划线"),
+    ("whitespace.remove", "删除"),
+    ("sanitize.title", "文件名清理规则"),
+    (
+        "sanitize.keep_unicode",
+        "保留各语言文字(关闭时只保留英文、数字和中文)",
+    ),
+    ("sanitize.invalid_chars", "不支持的字符:"),
+    ("sanitize.whitespace", "空白字符:"),
+    ("sanitize.max_length", "最大长度(0 为不限制):"),
+    (
+        "sanitize.avoid_reserved",
+        "避开 Windows 保留名(CON、NUL 等)",
+    ),
+    ("sanitize.sample", "示例:"),
+    ("sanitize.result", "清理后: {0}"),
+    // 输出校验
+    (
+        "verify.unreadable",
+        "无法读取输出文件, 文件可能不存在或已损坏",
+    ),
+    ("verify.no_video", "输出文件缺少视频流"),
+    ("verify.no_audio", "输出文件缺少音频流"),
+    (
+        "verify.duration_mismatch",
+        "输出时长 {0} 秒与预期 {1} 秒相差超过 {2} 秒",
+    ),
+    ("verify.no_duration", "无法读取输出文件时长"),
+    ("verify.decode_start_failed", "启动解码检查失败: {0}"),
+    ("verify.decode_error", "解码检查发现错误: {0}"),
+    ("verify.enabled", "处理后校验输出"),
+    ("verify.tolerance", "时长容差(秒):"),
+    ("verify.full_decode", "完整解码检查(较慢)"),
+    // 文件名模板
+    ("var.input_name", "源文件名(不含扩展名)"),
+    ("var.ext", "源文件扩展名"),
+    ("var.parent_dir", "源文件所在目录名"),
+    ("var.rotation", "旋转角度"),
+    ("var.start", "开始时间"),
+    ("var.end", "结束时间"),
+    ("var.duration", "输出时长"),
+    ("var.width", "视频宽度"),
+    ("var.height", "视频高度"),
+    ("var.codec", "视频编码"),
+    ("var.index", "在批次中的序号"),
+    ("var.segment", "同一源文件的第几段"),
+    ("var.timestamp", "当前时间, 默认 %Y%m%d%H%M%S"),
+    ("var.date", "当前日期, 默认 %Y-%m-%d"),
+    ("var.time", "当前时刻, 默认 %H-%M-%S"),
+    ("var.creation_date", "媒体创建日期, 默认 %Y-%m-%d"),
+    ("template.extra_brace", "多余的 \"}\": {0}"),
+    ("template.unclosed", "变量缺少 \"}\": {0}"),
+    ("template.empty", "文件名模板不能为空"),
+    ("template.relative", "模板只能包含输出目录下的相对路径"),
+    ("template.unknown_var", "未知变量: {0}"),
+    ("template.no_format", "变量 {0} 不支持自定义格式"),
+    ("template.bad_date_format", "日期格式无效: {0}"),
+    ("template.label", "文件名模板:"),
+    ("template.variables", "可用变量"),
+    (
+        "template.help",
+        "日期变量可指定格式, 如 {date:%Y%m}; 模板中的 / 会创建子目录",
+    ),
+    ("template.invalid", "模板无效: {0}"),
+    ("template.preview", "输出文件预览: {0}"),
+    ("template.error", "文件名模板: {0}"),
+    // 错误说明
+    ("error.verification", "输出校验失败: {0}"),
+    ("error.cancelled", "已取消"),
+    ("error.no_code", "无"),
+    ("error.with_code", "{0} (退出码: {1})"),
+    (
+        "error.verification.explanation",
+        "ffmpeg 报告成功, 但输出文件缺少预期的流或无法完整解码。",
+    ),
+    (
+        "error.cancelled.explanation",
+        "处理被用户停止, 输出文件可能不完整。",
+    ),
+    ("error.setup.suggestion", "请检查输出目录和处理参数。"),
+    (
+        "error.verification.suggestion",
+        "查看任务日志, 尝试改用精确裁剪或重新编码, 必要时检查源文件。",
+    ),
+    ("error.cancelled.suggestion", "重新开始处理即可。"),
+    ("ffmpeg.unknown_encoder", "未知编码器 {0}"),
+    ("ffmpeg.invalid_data", "输入数据无效"),
+    ("ffmpeg.permission_denied", "没有访问权限"),
+    ("ffmpeg.codec_container", "容器不支持该编码"),
+    ("ffmpeg.no_space", "磁盘空间不足"),
+    ("ffmpeg.not_found", "文件或目录不存在"),
+    ("ffmpeg.invalid_option", "无效的参数 {0}"),
+    ("ffmpeg.unknown", "FFmpeg处理失败"),
+    (
+        "ffmpeg.unknown_encoder.explanation",
+        "当前安装的 ffmpeg 不包含编码器 \"{0}\", 可能是名称拼写错误或编译时未启用。",
+    ),
+    (
+        "ffmpeg.invalid_data.explanation",
+        "ffmpeg 无法解析输入文件, 文件可能已损坏、不完整或不是媒体文件。",
+    ),
+    (
+        "ffmpeg.permission_denied.explanation",
+        "ffmpeg 没有读取输入文件或写入输出位置的权限。",
+    ),
+    (
+        "ffmpeg.codec_container.explanation",
+        "所选的音视频编码无法写入输出文件的容器格式。",
+    ),
+    ("ffmpeg.no_space.explanation", "写入输出文件时磁盘已满。"),
+    ("ffmpeg.not_found.explanation", "输入文件或输出目录不存在。"),
+    (
+        "ffmpeg.invalid_option.explanation",
+        "ffmpeg 不认识参数 \"{0}\" 或其取值无效。",
+    ),
+    (
+        "ffmpeg.unknown.explanation",
+        "未能识别失败原因, 请查看任务日志。",
+    ),
+    (
+        "ffmpeg.unknown_encoder.suggestion",
+        "运行 ffmpeg -encoders 查看可用编码器, 或改用 copy/libx264 等常见编码器。",
+    ),
+    (
+        "ffmpeg.invalid_data.suggestion",
+        "用播放器确认文件能否正常播放, 必要时重新拷贝源文件。",
+    ),
+    (
+        "ffmpeg.permission_denied.suggestion",
+        "检查文件和输出目录的权限, 或选择其他输出目录。",
+    ),
+    (
+        "ffmpeg.codec_container.suggestion",
+        "改用兼容的编码(如 MP4 使用 aac 音频), 或换用 MKV 等更通用的容器。",
+    ),
+    (
+        "ffmpeg.no_space.suggestion",
+        "清理磁盘空间或将输出目录改到其他磁盘。",
+    ),
+    (
+        "ffmpeg.not_found.suggestion",
+        "确认源文件仍在原位置, 并检查输出路径。",
+    ),
+    (
+        "ffmpeg.invalid_option.suggestion",
+        "检查额外输入/输出参数的拼写和取值。",
+    ),
+    (
+        "ffmpeg.unknown.suggestion",
+        "打开日志面板查看 ffmpeg 的完整输出。",
+    ),
+    // 界面字体
+    ("font.title", "界面字体"),
+    ("font.path", "字体文件:"),
+    ("font.auto_hint", "留空则自动查找中文字体"),
+    ("font.filter", "字体文件"),
+    ("font.size", "字号:"),
+    ("font.current", "当前字体: {0}"),
+    ("font.default", "未找到中文字体, 正在使用 egui 自带字体"),
+    ("font.loaded", "已加载字体: {0}"),
+    ("font.not_found", "未找到可用的中文字体, 使用 egui 自带字体"),
+    ("font.read_failed", "无法读取字体 {0}: {1}"),
+    ("font.invalid", "不是有效的字体文件: {0}"),
+    // 处理过程
+    ("process.mkdir_failed", "创建目录失败: {0}"),
+    ("process.spawn_failed", "启动FFmpeg失败: {0}"),
+    ("process.no_stderr", "无法获取stderr管道"),
+    ("process.wait_failed", "等待FFmpeg进程失败: {0}"),
+    // 界面
+    ("ui.app_title", "视频处理工具"),
+    ("ui.language", "语言:"),
+    ("ui.language_system", "跟随系统"),
+    ("ui.drop_hint", "拖放文件到此区域或使用下方按钮添加文件"),
+    ("ui.start_preview", "开始时间预览 (HH:MM:SS):"),
+    ("ui.end_preview", "结束时间预览 (HH:MM:SS):"),
+    ("ui.generate_preview", "🔄 生成预览"),
+    ("ui.start_preview_loading", "正在生成开始时间预览..."),
+    ("ui.end_preview_loading", "正在生成结束时间预览..."),
+    ("ui.selected_files", "已选文件:"),
+    ("ui.file_count", "{0} 个文件"),
+    ("ui.clear_list", "清空列表"),
+    ("ui.copy_params", "复制选中参数"),
+    ("ui.no_file", "尚未选择任何视频文件。"),
+    ("ui.video_info", "视频基本信息"),
+    ("ui.video_duration", "视频长度: {0}"),
+    ("ui.video_size", "视频大小: {0}"),
+    ("ui.video_format", "视频格式: {0}"),
+    ("ui.settings", "参数设置"),
+    ("ui.output_dir", "输出目录:"),
+    ("ui.current_file", "当前文件: {0}"),
+    (
+        "ui.editing_defaults",
+        "当前编辑: 默认参数(用于新添加的文件)",
+    ),
+    ("ui.apply_all", "应用到全部文件"),
+    ("ui.start_time", "开始时间:"),
+    ("ui.end_time", "结束时间:"),
+    ("ui.rotation", "旋转角度:"),
+    ("ui.trim_mode", "裁剪方式:"),
+    ("ui.extra_input", "额外输入参数:"),
+    ("ui.extra_output", "额外输出参数:"),
+    ("ui.global_input", "全局输入参数:"),
+    ("ui.global_output", "全局输出参数:"),
+    ("ui.log_dir", "日志目录:"),
+    ("ui.log_dir_hint", "留空则保存在输出文件旁"),
+    ("ui.command_preview", "命令预览"),
+    ("ui.command_hint", "选择文件后显示将要执行的 ffmpeg 命令"),
+    ("ui.copy_command", "复制命令"),
+    ("ui.arg_list", "参数列表"),
+    ("ui.invalid_command", "命令无效: {0}"),
+    ("ui.start", "开始处理"),
+    ("ui.stop", "停止"),
+    ("ui.continue_on_error", "出错后继续处理其余文件"),
+    ("ui.on_conflict", "输出已存在时:"),
+    ("ui.task", "任务 {0}"),
+    ("ui.task_item", "任务 {0}: {1}"),
+    ("ui.progress", "进度: {0}%"),
+    ("ui.results", "任务结果"),
+    ("enc.video_codec", "视频编码:"),
+    ("enc.width", "宽"),
+    ("enc.height", "高"),
+    ("enc.video_filters", "视频滤镜:"),
+    ("enc.filters_hint", "例如 hflip,eq=contrast=1.1"),
+    ("enc.container", "输出容器:"),
+    ("enc.same_container", "与源文件相同"),
+    ("enc.audio_codec", "音频编码:"),
+    ("enc.audio_bitrate", "码率(kbps)"),
+    // 快捷键
+    ("shortcut.title", "快捷键"),
+    ("shortcut.mark_in", "将播放头位置设为开始时间"),
+    ("shortcut.mark_out", "将播放头位置设为结束时间"),
+    ("shortcut.step_frame", "后退 / 前进一帧"),
+    ("shortcut.step_second", "后退 / 前进一秒"),
+    ("shortcut.play", "播放 / 暂停"),
+    ("shortcut.start", "开始处理"),
+    ("shortcut.remove", "移除选中的文件"),
+    ("shortcut.help", "显示 / 隐藏本帮助"),
+    (
+        "shortcut.typing_note",
+        "输入框有焦点时只响应 Ctrl + Enter、F1 和 Esc。",
+    ),
+    ("playhead.title", "播放头:"),
+    ("playhead.mark_in", "设为开始 (I)"),
+    ("playhead.mark_out", "设为结束 (O)"),
+    // 监视文件夹
+    ("watch.title", "监视文件夹"),
+    ("watch.input_dir", "监视目录:"),
+    ("watch.output_dir", "输出目录:"),
+    ("watch.archive_dir", "归档目录:"),
+    (
+        "watch.dir_hint",
+        "输出目录留空则使用预设中的目录; 归档目录留空则不移动源文件。",
+    ),
+    ("watch.preset", "预设:"),
+    ("watch.default_preset", "默认设置"),
+    ("watch.stable_secs", "文件大小保持不变多少秒后处理:"),
+    ("watch.include_existing", "同时处理目录中已有的文件"),
+    ("watch.start", "开始监视"),
+    ("watch.stop", "停止监视"),
+    ("watch.input_missing", "监视目录不存在: {0}"),
+    ("watch.archive_same", "归档目录不能与监视目录相同"),
+    ("watch.output_same", "输出目录不能与监视目录相同"),
+    ("watch.watching", "正在监视 {0} 中的新文件"),
+    ("watch.stopped", "已停止监视 {0}"),
+    ("watch.found", "新文件已就绪: {0}"),
+    ("watch.failed", "处理 {0} 失败: {1}"),
+    ("watch.archived", "已将 {0} 移动到 {1}"),
+    ("watch.archive_failed", "无法归档 {0}: {1}"),
+    // 添加文件
+    ("import.options", "文件过滤"),
+    ("import.extensions", "扩展名:"),
+    ("import.extensions_hint", "留空则接受所有扩展名"),
+    ("import.validate", "添加前用 ffprobe 检查文件"),
+    ("import.scanning", "正在扫描和检查文件..."),
+    ("import.summary", "已添加 {0} 个文件, 跳过 {1} 项"),
+    ("import.skipped_count", "跳过了 {0} 项"),
+    ("import.skipped", "已跳过 {0}: {1}"),
+    ("import.dismiss", "关闭"),
+    ("import.duplicate", "已在列表中"),
+    ("import.extension", "扩展名不在过滤列表中"),
+    ("import.too_deep", "文件夹层级过深"),
+    ("import.invalid", "无法识别的媒体文件: {0}"),
+    ("import.no_streams", "没有音频或视频流"),
+    (
+        "import.probe_unavailable",
+        "无法运行 ffprobe, 添加文件时不做检查: {0}",
+    ),
+    // 文件列表
+    ("files.add_files", "添加文件..."),
+    ("files.add_folder", "添加文件夹..."),
+    ("files.remove_checked", "移除选中"),
+    ("files.checked_count", "(已选 {0} 个)"),
+    ("files.media_filter", "媒体文件"),
+    ("files.name", "文件名"),
+    ("files.duration", "时长"),
+    ("files.resolution", "分辨率"),
+    ("files.codec", "编码"),
+    ("files.size", "大小"),
+    ("files.status", "状态"),
+    ("files.missing", "文件不存在"),
+    (
+        "files.drag_hint",
+        "拖动调整顺序。按住 Ctrl 或 Shift 单击文件名可选择多个文件。",
+    ),
+    // 重复文件
+    ("duplicate.check", "查找重复文件"),
+    ("duplicate.perceptual", "比较画面"),
+    (
+        "duplicate.perceptual_hint",
+        "通过比较抽样画面找出重新编码或缩放过的副本, 每个文件需要多次调用 ffmpeg。",
+    ),
+    ("duplicate.threshold", "容差:"),
+    ("duplicate.scanning", "正在比较文件..."),
+    ("duplicate.summary", "{0} 个文件存在重复"),
+    ("duplicate.select", "选中多余的副本"),
+    ("duplicate.identical_to", "内容相同:\n{0}"),
+    ("duplicate.similar_to", "画面相似:\n{0}"),
+    ("duplicate.found", "重复文件: {0}"),
+    // 变速
+    ("speed.factor", "速度:"),
+    ("speed.drop_audio", "去掉音频"),
+    ("speed.reverse", "倒放"),
+    (
+        "speed.reverse_hint",
+        "倒放需要把整段画面缓存在内存中, 片段最长 {0} 秒。",
+    ),
+    ("speed.factor_range", "速度 {0} 超出范围 {1}-{2}"),
+    (
+        "speed.reverse_too_long",
+        "要倒放的片段长 {0}, 最长只能 {1}, 请缩短开始/结束时间范围",
+    ),
+    (
+        "speed.video_copy",
+        "变速或倒放需要重新编码: 请选择 copy 以外的视频编码",
+    ),
+    (
+        "speed.audio_copy",
+        "变速或倒放需要重新编码: 请选择 copy 以外的音频编码, 或去掉音频",
+    ),
+    (
+        "speed.nothing_left",
+        "输出容器只包含音频, 去掉音频后没有可写入的内容",
+    ),
+];
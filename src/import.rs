@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::i18n::t;
+use crate::logging;
+use crate::media::MEDIA_EXTENSIONS;
+
+// 子目录最多向下展开的层数
+const MAX_DEPTH: usize = 16;
+
+// 添加文件时的过滤设置
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImportSettings {
+    pub extensions: String, // 逗号分隔的扩展名, 为空时不按扩展名过滤
+    pub validate: bool,     // 是否用 ffprobe 确认文件包含音视频流
+}
+
+impl Default for ImportSettings {
+    fn default() -> Self {
+        Self {
+            extensions: MEDIA_EXTENSIONS.join(", "),
+            validate: true,
+        }
+    }
+}
+
+impl ImportSettings {
+    pub fn extension_list(&self) -> Vec<String> {
+        self.extensions
+            .split([',', ' ', ';'])
+            .map(|e| e.trim().trim_start_matches('.').to_lowercase())
+            .filter(|e| !e.is_empty())
+            .collect()
+    }
+}
+
+// 未被添加的文件及原因
+#[derive(Clone)]
+pub struct Skipped {
+    pub path: PathBuf,
+    pub reason: String,
+}
+
+#[derive(Default)]
+pub struct Collected {
+    pub files: Vec<PathBuf>,
+    pub skipped: Vec<Skipped>,
+}
+
+// 展开拖入的文件夹并过滤文件. existing 为列表中已有的文件, 不会重复添加
+pub fn collect(paths: &[PathBuf], settings: &ImportSettings, existing: &[String]) -> Collected {
+    let extensions = settings.extension_list();
+    let mut candidates = Vec::new();
+    let mut result = Collected::default();
+    for path in paths {
+        if path.is_dir() {
+            walk(path, 0, &mut candidates, &mut result.skipped);
+        } else {
+            candidates.push(path.clone());
+        }
+    }
+
+    // 无法启动 ffprobe 时不再逐个检查
+    let mut validate = settings.validate;
+    for path in candidates {
+        let path_str = path.display().to_string();
+        if existing.contains(&path_str) || result.files.contains(&path) {
+            result.skipped.push(Skipped {
+                path,
+                reason: t!("import.duplicate").to_string(),
+            });
+            continue;
+        }
+        if !extensions.is_empty() && !has_extension(&path, &extensions) {
+            result.skipped.push(Skipped {
+                path,
+                reason: t!("import.extension").to_string(),
+            });
+            continue;
+        }
+        if validate {
+            match probe(&path) {
+                Ok(()) => {}
+                Err(ProbeError::Unavailable(e)) => {
+                    logging::log(t!("import.probe_unavailable", e));
+                    validate = false;
+                }
+                Err(ProbeError::Invalid(reason)) => {
+                    result.skipped.push(Skipped { path, reason });
+                    continue;
+                }
+            }
+        }
+        result.files.push(path);
+    }
+
+    for skipped in &result.skipped {
+        logging::log(t!("import.skipped", skipped.path.display(), skipped.reason));
+    }
+    result
+}
+
+// 按文件名顺序递归列出目录中的文件, 跳过隐藏文件
+fn walk(dir: &Path, depth: usize, files: &mut Vec<PathBuf>, skipped: &mut Vec<Skipped>) {
+    let mut entries: Vec<_> = match fs::read_dir(dir) {
+        Ok(entries) => entries.flatten().collect(),
+        Err(e) => {
+            skipped.push(Skipped {
+                path: dir.to_path_buf(),
+                reason: e.to_string(),
+            });
+            return;
+        }
+    };
+    entries.sort_by_key(|e| e.file_name());
+    for entry in entries {
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        let path = entry.path();
+        // 不跟随指向目录的符号链接, 避免循环
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if is_dir {
+            if depth < MAX_DEPTH {
+                walk(&path, depth + 1, files, skipped);
+            } else {
+                skipped.push(Skipped {
+                    path,
+                    reason: t!("import.too_deep").to_string(),
+                });
+            }
+        } else if path.is_file() {
+            files.push(path);
+        }
+    }
+}
+
+fn has_extension(path: &Path, extensions: &[String]) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| extensions.contains(&e))
+}
+
+enum ProbeError {
+    Unavailable(String), // 无法运行 ffprobe
+    Invalid(String),     // 不是可识别的音视频文件
+}
+
+// 用 ffprobe 检查文件能否解析并包含音视频流
+fn probe(path: &Path) -> Result<(), ProbeError> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "stream=codec_type",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| ProbeError::Unavailable(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|l| !l.trim().is_empty())
+            .unwrap_or_default()
+            .trim()
+            .to_string();
+        return Err(ProbeError::Invalid(t!("import.invalid", reason)));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout
+        .lines()
+        .any(|l| matches!(l.trim(), "video" | "audio"))
+    {
+        return Err(ProbeError::Invalid(t!("import.no_streams").to_string()));
+    }
+    Ok(())
+}
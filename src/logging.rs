@@ -0,0 +1,56 @@
+use chrono::Local;
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex;
+
+use crate::i18n::t;
+
+// 内存中最多保留的日志行数
+const MAX_LINES: usize = 5000;
+
+static APP_LOG: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+// 记录程序诊断信息, 同时输出到 stderr
+pub fn log(message: impl AsRef<str>) {
+    let line = format!("[{}] {}", Local::now().format("%H:%M:%S"), message.as_ref());
+    eprintln!("{}", line);
+    push_capped(&mut APP_LOG.lock().unwrap(), line);
+}
+
+pub fn app_log_lines() -> Vec<String> {
+    APP_LOG.lock().unwrap().clone()
+}
+
+// 追加一行并丢弃超出上限的旧行
+pub fn push_capped(lines: &mut Vec<String>, line: String) {
+    lines.push(line);
+    if lines.len() > MAX_LINES {
+        let excess = lines.len() - MAX_LINES;
+        lines.drain(..excess);
+    }
+}
+
+// 任务日志路径: 未设置日志目录时保存在输出文件旁
+pub fn log_path_for(output_path: &str, log_dir: &str) -> String {
+    let output = Path::new(output_path);
+    let file_name = format!(
+        "{}.log",
+        output
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "ffmpeg".to_string())
+    );
+    let dir = if log_dir.trim().is_empty() {
+        output.parent().unwrap_or(Path::new(".")).to_path_buf()
+    } else {
+        Path::new(log_dir.trim()).to_path_buf()
+    };
+    dir.join(file_name).to_string_lossy().into_owned()
+}
+
+pub fn read_log_file(path: &str) -> String {
+    match fs::read(path) {
+        Ok(data) => String::from_utf8_lossy(&data).into_owned(),
+        Err(e) => t!("log.read_failed", path, e),
+    }
+}
@@ -1,38 +1,149 @@
-use chrono::Local;
-use chrono::NaiveTime;
 use eframe::egui;
-use egui::{FontDefinitions, FontFamily, FontId};
-use regex::Regex;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::io::BufRead;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
+mod batch;
+mod cli;
+mod collision;
+mod command;
+mod container;
+mod duplicates;
+mod ffmpeg_error;
+mod file_list;
+mod fonts;
+mod handbrake;
+mod i18n;
+mod import;
+mod logging;
+mod media;
+mod playhead;
+mod preset;
+mod project;
+mod report;
+mod sanitize;
+mod shortcuts;
+mod speed;
+mod template;
+mod timecode;
+mod verify;
+mod watch;
+
+use batch::{TaskResult, TaskStatus};
+use collision::{CollisionPolicy, Conflict, OutputAction};
+use command::ExtraArgs;
+use duplicates::{DuplicateSettings, MatchKind};
+use ffmpeg_error::TaskError;
+use file_list::{RowCache, SortColumn};
+use fonts::FontSettings;
+use i18n::{Language, t};
+use import::ImportSettings;
+use playhead::Playhead;
+use preset::{AUDIO_CODECS, EncodingSettings, Preset, TrimMode, VIDEO_CODECS};
+use sanitize::{InvalidCharMode, SanitizeRules, WhitespaceMode};
+use shortcuts::Action;
+use speed::SpeedSettings;
+use template::TemplateInput;
+use timecode::TimeSpec;
+use verify::VerifySettings;
+use watch::{WatchHandle, WatchSettings};
+
+const DEFAULT_TEMPLATE: &str = "{input_name}_processed_{rotation}_{timestamp}";
+
 #[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
 struct AppConfig {
     output_dir: String,
+    presets: Vec<Preset>,
+    global_args: ExtraArgs,
+    log_dir: String, // 任务日志目录, 为空时保存在输出文件旁
+    continue_on_error: bool,
+    verify: VerifySettings,
+    sanitize: SanitizeRules,
+    collision_policy: CollisionPolicy,
+    language: Option<Language>, // 为空时跟随系统区域设置
+    font: FontSettings,
+    watch: WatchSettings,
+    import: ImportSettings,
+    duplicates: DuplicateSettings,
+}
+
+fn default_config_path() -> String {
+    format!("{}/.config/ffmpeg-gui.config", env!("HOME"))
+}
+
+fn read_config(path: &str) -> Option<AppConfig> {
+    let config_str = fs::read_to_string(path).ok()?;
+    serde_json::from_str::<AppConfig>(&config_str).ok()
 }
 
+// 播放头截取的画面: (请求代数, 图像数据), 空数据表示取帧失败
+type PlayheadFrame = (u64, Vec<u8>);
+
 struct VideoProcessor {
     // 文件参数
-    source_paths: Vec<String>,
+    sources: Vec<SourceEntry>,
+    selected: Option<usize>, // 当前选中的文件(参数面板编辑对象)
     output_dir: String,
     output_template: String,
     config_path: String,
 
-    // 处理参数
-    start_time: String,
-    end_time: String,
-    rotation: i32,
+    // 处理参数(新添加文件的默认值)
+    default_params: TaskParams,
+    global_args: ExtraArgs, // 对所有任务生效的追加参数
+    log_dir: String,
+    continue_on_error: bool, // 某个任务失败后是否继续处理其余任务
+    verify: VerifySettings,
+    sanitize: SanitizeRules, // 输出文件名清理规则
+    sanitize_sample: String, // 清理规则预览用的示例文件名
+    collision_policy: CollisionPolicy,
+    pending_conflicts: Vec<Conflict>, // 等待用户决定的输出冲突
+    language: Option<Language>,
+    font: FontSettings,
+    loaded_font: Option<PathBuf>, // 实际加载的字体文件, None 表示 egui 自带字体
+    watch: WatchSettings,
+    watch_handle: Option<WatchHandle>, // 正在运行的监视任务
+    watch_error: String,
+
+    // 添加文件
+    import: ImportSettings,
+    imports_running: usize, // 正在后台展开和检查的添加操作数
+    import_results: Arc<Mutex<Vec<import::Collected>>>,
+    skipped_files: Vec<import::Skipped>, // 最近添加时被跳过的文件
+
+    // 文件列表
+    sort: Option<(SortColumn, bool)>, // 排序列及是否升序, 拖动调整顺序后清除
+    dragging_source: Option<usize>,   // 正在拖动的文件下标
+    file_rows: RowCache,
+
+    // 重复文件检测
+    duplicate_settings: DuplicateSettings,
+    duplicate_groups: Vec<duplicates::Group>,
+    duplicate_results: Arc<Mutex<Option<Vec<duplicates::Group>>>>,
+    duplicate_scanning: bool,
+    duplicate_rescan: bool, // 检测期间列表有变化, 结束后需要重新检测
+
+    // 预设
+    presets: Vec<Preset>,
+    selected_preset: Option<usize>,
+    preset_name_input: String,
+    preset_error: String,
+    import_report: Vec<String>, // 最近一次导入 HandBrake 预设的结果
 
     // 状态管理
     batch_queue: Vec<BatchTask>,
     processing: Arc<Mutex<bool>>,
     state: ProcessingState,
 
+    // 日志面板
+    show_log_panel: bool,
+    log_source: LogSource,
+    log_file_cache: Option<(String, String)>, // (日志路径, 内容)
+
     // 新增预览相关字段
     start_preview_texture: Option<egui::TextureHandle>, // 开始时间预览纹理
     end_preview_texture: Option<egui::TextureHandle>,   // 结束时间预览纹理
@@ -45,6 +156,15 @@ struct VideoProcessor {
     last_preview_request_time: f64,                     // 上次预览请求时间(用于防抖)
     preview_thread: Option<std::thread::JoinHandle<()>>, // 预览线程句柄
 
+    // 播放头预览
+    playhead: Playhead,
+    playhead_texture: Option<egui::TextureHandle>,
+    playhead_frame: Arc<Mutex<Option<PlayheadFrame>>>,
+    playhead_loading: bool,
+    playhead_generation: u64,         // 切换文件时递增, 丢弃旧文件的截取结果
+    playhead_frame_time: Option<f64>, // 当前纹理对应的位置
+    show_shortcuts: bool,
+
     // 视频基本信息
     video_duration: String,
     video_size: String,
@@ -55,32 +175,103 @@ struct VideoProcessor {
 struct ProcessingState {
     progress: Arc<Mutex<f32>>,
     message: Arc<Mutex<String>>,
+    live_log: Arc<Mutex<Vec<String>>>, // 当前任务的 ffmpeg 输出
+    last_error: Arc<Mutex<Option<TaskError>>>, // 最近一次失败的详细原因
+    results: Arc<Mutex<Vec<TaskResult>>>, // 与 batch_queue 一一对应的任务状态
+    cancel_requested: Arc<Mutex<bool>>,
 }
 
-#[derive(Clone)]
-struct BatchTask {
-    input_path: String,
-    output_path: String,
+// 日志面板显示的内容
+#[derive(Clone, Copy, PartialEq)]
+enum LogSource {
+    Live,        // 正在运行的任务
+    App,         // 程序诊断信息
+    Task(usize), // 批处理队列中任务的日志文件
+}
+
+// 单个文件的处理参数
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+struct TaskParams {
     start_time: String,
     end_time: String,
+    trim_mode: TrimMode,
     rotation: i32,
+    encoding: EncodingSettings,
+    speed: SpeedSettings,
+    extra_args: ExtraArgs,
+}
+
+impl Default for TaskParams {
+    fn default() -> Self {
+        Self {
+            start_time: "0:00:00".to_owned(),
+            end_time: "0:00:00".to_owned(),
+            trim_mode: TrimMode::default(),
+            rotation: 0,
+            encoding: EncodingSettings::default(),
+            speed: SpeedSettings::default(),
+            extra_args: ExtraArgs::default(),
+        }
+    }
+}
+
+// 文件列表中的一项: 源文件路径及其独立的处理参数
+#[derive(Clone, Serialize, Deserialize)]
+struct SourceEntry {
+    path: String,
+    params: TaskParams,
+    #[serde(skip)]
+    checked: bool, // 在文件列表中被多选
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct BatchTask {
+    input_path: String,
+    output_path: String,
+    params: TaskParams,
+    #[serde(default)]
+    global_args: ExtraArgs,
+    #[serde(default)]
+    log_path: String, // ffmpeg 完整输出的保存位置
+    #[serde(default)]
+    output_action: OutputAction,
 }
 
 impl VideoProcessor {
     fn load_config(&mut self) {
-        let config_path = Path::new(&self.config_path);
-        if config_path.exists() {
-            if let Ok(config_str) = fs::read_to_string(config_path) {
-                if let Ok(config) = serde_json::from_str::<AppConfig>(&config_str) {
-                    self.output_dir = config.output_dir;
-                }
-            }
+        if let Some(config) = read_config(&self.config_path) {
+            self.output_dir = config.output_dir;
+            self.presets = config.presets;
+            self.global_args = config.global_args;
+            self.log_dir = config.log_dir;
+            self.continue_on_error = config.continue_on_error;
+            self.verify = config.verify;
+            self.sanitize = config.sanitize;
+            self.collision_policy = config.collision_policy;
+            self.language = config.language;
+            self.font = config.font;
+            self.watch = config.watch;
+            self.import = config.import;
+            self.duplicate_settings = config.duplicates;
         }
     }
 
     fn save_config(&self) {
         let config = AppConfig {
             output_dir: self.output_dir.clone(),
+            presets: self.presets.clone(),
+            global_args: self.global_args.clone(),
+            log_dir: self.log_dir.clone(),
+            continue_on_error: self.continue_on_error,
+            verify: self.verify.clone(),
+            sanitize: self.sanitize.clone(),
+            collision_policy: self.collision_policy,
+            language: self.language,
+            font: self.font.clone(),
+            watch: self.watch.clone(),
+            import: self.import.clone(),
+            duplicates: self.duplicate_settings.clone(),
         };
         if let Ok(config_str) = serde_json::to_string_pretty(&config) {
             let _ = fs::create_dir_all(Path::new(&self.config_path).parent().unwrap());
@@ -91,18 +282,51 @@ impl VideoProcessor {
 
 impl Default for VideoProcessor {
     fn default() -> Self {
-        let config_path = format!("{}/.config/ffmpeg-gui.config", env!("HOME"));
+        let config_path = default_config_path();
         let mut processor = Self {
-            source_paths: Vec::new(),
+            sources: Vec::new(),
+            selected: None,
             output_dir: "output".to_string(),
-            output_template: "{input_name}_processed_{rotation}_{timestamp}".to_string(),
+            output_template: DEFAULT_TEMPLATE.to_string(),
             config_path,
-            start_time: "0:00:00".to_owned(),
-            end_time: "0:00:00".to_owned(),
-            rotation: 0,
+            default_params: TaskParams::default(),
+            global_args: ExtraArgs::default(),
+            log_dir: String::new(),
+            continue_on_error: false,
+            verify: VerifySettings::default(),
+            sanitize: SanitizeRules::default(),
+            collision_policy: CollisionPolicy::default(),
+            pending_conflicts: Vec::new(),
+            language: None,
+            font: FontSettings::default(),
+            loaded_font: None,
+            watch: WatchSettings::default(),
+            watch_handle: None,
+            watch_error: String::new(),
+            import: ImportSettings::default(),
+            imports_running: 0,
+            import_results: Arc::new(Mutex::new(Vec::new())),
+            skipped_files: Vec::new(),
+            sort: None,
+            dragging_source: None,
+            file_rows: RowCache::default(),
+            duplicate_settings: DuplicateSettings::default(),
+            duplicate_groups: Vec::new(),
+            duplicate_results: Arc::new(Mutex::new(None)),
+            duplicate_scanning: false,
+            duplicate_rescan: false,
+            sanitize_sample: "My Clip: café 日本語 ファイル?.mp4".to_string(),
+            presets: Vec::new(),
+            selected_preset: None,
+            preset_name_input: String::new(),
+            preset_error: String::new(),
+            import_report: Vec::new(),
             batch_queue: Vec::new(),
             processing: Arc::new(Mutex::new(false)),
             state: ProcessingState::default(),
+            show_log_panel: true,
+            log_source: LogSource::Live,
+            log_file_cache: None,
             start_preview_texture: None,
             end_preview_texture: None,
             start_preview_time: "0:00:00".to_owned(),
@@ -113,6 +337,13 @@ impl Default for VideoProcessor {
             current_end_preview_frame: Arc::new(Mutex::new(None)),
             last_preview_request_time: 0.0,
             preview_thread: None,
+            playhead: Playhead::default(),
+            playhead_texture: None,
+            playhead_frame: Arc::new(Mutex::new(None)),
+            playhead_loading: false,
+            playhead_generation: 0,
+            playhead_frame_time: None,
+            show_shortcuts: false,
             video_duration: "".to_string(),
             video_size: "".to_string(),
             video_format: "".to_string(),
@@ -123,10 +354,40 @@ impl Default for VideoProcessor {
 }
 
 impl eframe::App for VideoProcessor {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+    fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        let language = i18n::language();
         // 处理文件拖放
         self.handle_file_drop(ctx);
 
+        // 处理过程中定时刷新进度和日志
+        if *self.processing.lock().unwrap() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(200));
+        } else if self.watch_handle.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_secs(1));
+        }
+
+        // 快捷键和播放头
+        self.handle_shortcuts(ctx);
+        let duration = self.selected_duration();
+        self.playhead.tick(ctx.input(|i| i.time), duration);
+        if self.playhead.playing {
+            ctx.request_repaint();
+        }
+
+        // 输出冲突确认窗口
+        self.conflict_dialog(ctx);
+        self.shortcut_help(ctx);
+
+        // 底部日志面板
+        if self.show_log_panel {
+            egui::TopBottomPanel::bottom("log_panel")
+                .resizable(true)
+                .default_height(200.0)
+                .show(ctx, |ui| {
+                    self.log_panel(ui);
+                });
+        }
+
         // 右侧预览面板
         egui::SidePanel::right("preview_panel")
             .resizable(true)
@@ -139,26 +400,44 @@ impl eframe::App for VideoProcessor {
         egui::CentralPanel::default().show(ctx, |ui| {
             // 调整 间隙
             ui.spacing_mut().item_spacing = egui::vec2(10.0, 30.0);
-            ui.heading("视频处理工具");
+            // 内容较多时可滚动
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading(t!("ui.app_title"));
+                    self.language_selector(ui);
+                });
+
+                // 拖放提示
+                ui.label(t!("ui.drop_hint"));
+
+                // 文件管理区域
+                self.file_management_panel(ui);
+                self.import_panel(ui);
 
-            // 拖放提示
-            ui.label("拖放文件到此区域或使用下方按钮添加文件");
+                // 视频基本信息
+                self.video_info_panel(ui);
 
-            // 文件管理区域
-            self.file_management_panel(ui);
+                // 参数设置
+                self.settings_panel(ui, ctx);
 
-            // 视频基本信息
-            self.video_info_panel(ui);
+                // 命令预览
+                self.command_preview_panel(ui);
 
-            // 参数设置
-            self.settings_panel(ui, ctx);
+                // 处理控制
+                self.verify_settings_panel(ui);
+                self.process_control(ui);
 
-            // 处理控制
-            self.process_control(ui);
+                // 进度显示
+                self.progress_display(ui);
 
-            // 进度显示
-            self.progress_display(ui);
+                // 监视文件夹
+                self.watch_panel(ui);
+            });
         });
+
+        if i18n::language() != language {
+            frame.set_window_title(t!("ui.app_title"));
+        }
     }
 }
 
@@ -175,17 +454,61 @@ fn load_image(data: &[u8]) -> Option<egui::ColorImage> {
     ))
 }
 
+// 用 ffmpeg 截取指定时间的一帧, 输出 JPEG 数据
+fn extract_frame(input_path: &str, time: &str, rotation: i32) -> Option<Vec<u8>> {
+    let mut args = vec!["-ss", time, "-i", input_path];
+
+    // 仅当旋转角度非0时添加旋转滤镜
+    let rotation_filter = format!("rotate=-{}*PI/180", rotation);
+    if rotation != 0 {
+        args.extend_from_slice(&["-vf", &rotation_filter]);
+    }
+
+    args.extend_from_slice(&[
+        "-vframes",
+        "1",
+        "-q:v",
+        "2",
+        "-f",
+        "image2pipe",
+        "-vcodec",
+        "mjpeg",
+        "-",
+    ]);
+
+    let output = match Command::new("ffmpeg")
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+    {
+        Ok(output) => output,
+        Err(e) => {
+            logging::log(t!("process.spawn_failed", e));
+            return None;
+        }
+    };
+    if !output.status.success() {
+        logging::log(t!(
+            "log.preview_failed",
+            format!("{:?}", output.status.code())
+        ));
+        return None;
+    }
+    Some(output.stdout).filter(|data| !data.is_empty())
+}
+
 // ffprobe 并解析其输出以获取视频的基本信息
 fn get_video_info(path: &str) -> (String, String, String) {
     // 验证文件存在
     if !Path::new(path).exists() {
-        eprintln!("文件不存在: {}", path);
+        logging::log(t!("log.file_missing", path));
         return ("".into(), "".into(), "".into());
     }
 
     // 执行命令
-    let output = Command::new("ffprobe")
-        .args(&[
+    let output = match Command::new("ffprobe")
+        .args([
             "-v",
             "error",
             "-select_streams",
@@ -202,12 +525,23 @@ fn get_video_info(path: &str) -> (String, String, String) {
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .output()
-        .expect("执行 ffprobe 失败");
+    {
+        Ok(output) => output,
+        Err(e) => {
+            // 未安装 ffprobe 等情况只记录日志, 不中断程序
+            logging::log(format!("{}: {}", t!("log.ffprobe_failed"), e));
+            return ("".into(), "".into(), "".into());
+        }
+    };
 
-    // 打印调试信息
-    println!("Exit Status: {}", output.status);
-    println!("stdout:\n{}", String::from_utf8_lossy(&output.stdout));
-    println!("stderr:\n{}", String::from_utf8_lossy(&output.stderr));
+    // 记录调试信息
+    logging::log(t!(
+        "log.ffprobe_status",
+        path,
+        output.status,
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    ));
 
     // 解析结果
     if output.status.success() {
@@ -226,6 +560,27 @@ fn get_video_info(path: &str) -> (String, String, String) {
     ("".into(), "".into(), "".into())
 }
 
+// 读取文件的总时长(秒)
+fn probe_duration(path: &str) -> Option<f64> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "default=noprint_wrappers=1:nokey=1",
+            path,
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
 fn format_duration(seconds: f64) -> String {
     let total = seconds as u64;
     let hours = total / 3600;
@@ -239,7 +594,7 @@ fn format_duration(seconds: f64) -> String {
 impl VideoProcessor {
     // 预览生成方法
     fn generate_preview(&mut self, ctx: &egui::Context, is_start_time: bool) {
-        if self.source_paths.is_empty()
+        if self.sources.is_empty()
             || (is_start_time && self.start_preview_loading)
             || (!is_start_time && self.end_preview_loading)
         {
@@ -258,12 +613,24 @@ impl VideoProcessor {
             thread.join().ok();
         }
 
-        let input_path = self.source_paths[0].clone();
-        let rotation = self.rotation;
+        // 预览选中的文件, 未选中时预览第一个文件
+        let entry = &self.sources[self.selected_index().unwrap_or(0)];
+        let input_path = entry.path.clone();
+        let rotation = entry.params.rotation;
         let time = if is_start_time {
-            self.start_preview_time.clone()
+            &self.start_preview_time
         } else {
-            self.end_preview_time.clone()
+            &self.end_preview_time
+        };
+        let seconds = TimeSpec::parse(time).and_then(|spec| {
+            spec.map_or(Ok(0.0), |spec| spec.to_seconds(&media::info(&input_path)))
+        });
+        let time = match seconds {
+            Ok(seconds) => timecode::format_seconds(seconds),
+            Err(e) => {
+                logging::log(e);
+                return;
+            }
         };
         let frame = if is_start_time {
             self.current_start_preview_frame.clone()
@@ -280,32 +647,8 @@ impl VideoProcessor {
         // 异步生成预览
         let ctx = ctx.clone();
         self.preview_thread = Some(std::thread::spawn(move || {
-            let temp_path = "preview_temp.jpg";
-
-            // 调用ffmpeg生成预览帧
-            let mut args = vec!["-ss", &time, "-i", &input_path];
-
-            // 仅当旋转角度非0时添加旋转滤镜
-            let rotation_filter = format!("rotate=-{}*PI/180", rotation);
-            if rotation != 0 {
-                args.extend_from_slice(&["-vf", &rotation_filter]);
-            }
-
-            args.extend_from_slice(&["-vframes", "1", "-q:v", "2", "-y", temp_path]);
-
-            // 修改后（添加状态检查）
-            let status = Command::new("ffmpeg")
-                .args(args)
-                .status()
-                .expect("Failed to execute ffmpeg");
-
-            if !status.success() {
-                eprintln!("Preview generation failed with code: {:?}", status.code());
-            }
-
-            // 读取生成的图片
-            let img_data = std::fs::read(temp_path).ok();
-            let _ = std::fs::remove_file(temp_path); // 清理临时文件
+            // 失败时写入空数据, 让界面结束加载状态
+            let img_data = Some(extract_frame(&input_path, &time, rotation).unwrap_or_default());
 
             // 更新到主线程
             let mut frame = frame.lock().unwrap();
@@ -331,18 +674,221 @@ impl VideoProcessor {
         if let Ok(mut frame) = self.current_end_preview_frame.try_lock() {
             *frame = None;
         }
+
+        self.reset_playhead();
+    }
+
+    // 执行本帧按下的快捷键
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let duration = self.selected_duration();
+        for action in shortcuts::pressed(ctx) {
+            match action {
+                Action::MarkIn => self.mark_at_playhead(ctx, true),
+                Action::MarkOut => self.mark_at_playhead(ctx, false),
+                Action::StepFrames(n) => {
+                    let fps = self
+                        .selected_index()
+                        .and_then(|i| media::info(&self.sources[i].path).frame_rate)
+                        .filter(|fps| *fps > 0.0)
+                        .unwrap_or(25.0);
+                    self.playhead.step(n as f64 / fps, duration);
+                }
+                Action::StepSeconds(n) => self.playhead.step(n as f64, duration),
+                Action::TogglePlay => {
+                    if self.selected_index().is_some() {
+                        self.playhead.toggle(duration);
+                    }
+                }
+                Action::StartProcessing => self.start_processing(),
+                Action::RemoveSelected => self.remove_sources(self.checked_indices()),
+                Action::ToggleHelp => self.show_shortcuts = !self.show_shortcuts,
+                Action::CloseHelp => self.show_shortcuts = false,
+            }
+        }
+    }
+
+    // 把播放头位置设为选中文件的开始或结束时间, 并刷新对应的预览
+    fn mark_at_playhead(&mut self, ctx: &egui::Context, is_start: bool) {
+        if self.selected_index().is_none() {
+            return;
+        }
+        let time = timecode::format_seconds(self.playhead.position);
+        let mut params = self.current_params().clone();
+        if is_start {
+            params.start_time = time.clone();
+            self.start_preview_time = time;
+        } else {
+            params.end_time = time.clone();
+            self.end_preview_time = time;
+        }
+        self.set_current_params(params);
+        // 标记后立即刷新预览, 不受防抖间隔限制
+        self.last_preview_request_time = 0.0;
+        self.generate_preview(ctx, is_start);
+    }
+
+    // 在后台截取播放头位置的画面, 同一时间只截取一帧
+    fn request_playhead_frame(&mut self, ctx: &egui::Context) {
+        let Some(i) = self.selected_index() else {
+            return;
+        };
+        let position = self.playhead.position;
+        if self.playhead_loading || self.playhead_frame_time == Some(position) {
+            return;
+        }
+        self.playhead_loading = true;
+        self.playhead_frame_time = Some(position);
+
+        let entry = &self.sources[i];
+        let input_path = entry.path.clone();
+        let rotation = entry.params.rotation;
+        let generation = self.playhead_generation;
+        let frame = self.playhead_frame.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let time = timecode::format_seconds(position);
+            let img_data = extract_frame(&input_path, &time, rotation).unwrap_or_default();
+            // 不覆盖更新一代的结果
+            let mut frame = frame.lock().unwrap();
+            if frame.as_ref().is_none_or(|(g, _)| *g <= generation) {
+                *frame = Some((generation, img_data));
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    // 播放头: 拖动、逐帧移动、播放, 以及设置开始/结束点
+    fn playhead_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some(i) = self.selected_index() else {
+            return;
+        };
+        let info = media::info(&self.sources[i].path);
+        let duration = info.duration;
+
+        ui.horizontal(|ui| {
+            ui.label(t!("playhead.title"));
+            let mut position = self.playhead.position;
+            let slider =
+                egui::Slider::new(&mut position, 0.0..=duration.unwrap_or(0.0)).show_value(false);
+            if ui.add_enabled(duration.is_some(), slider).changed() {
+                self.playhead.seek(position, duration);
+            }
+            ui.label(timecode::format_seconds(self.playhead.position));
+            if let Some(tc) = timecode::format_timecode(self.playhead.position, &info) {
+                ui.weak(tc.to_string());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            if ui
+                .button("-1s")
+                .on_hover_text(t!("shortcut.step_second"))
+                .clicked()
+            {
+                self.playhead.step(-1.0, duration);
+            }
+            if ui
+                .button("-1f")
+                .on_hover_text(t!("shortcut.step_frame"))
+                .clicked()
+            {
+                let fps = info.frame_rate.filter(|fps| *fps > 0.0).unwrap_or(25.0);
+                self.playhead.step(-1.0 / fps, duration);
+            }
+            let play_label = if self.playhead.playing { "⏸" } else { "▶" };
+            if ui
+                .button(play_label)
+                .on_hover_text(t!("shortcut.play"))
+                .clicked()
+            {
+                self.playhead.toggle(duration);
+            }
+            if ui
+                .button("+1f")
+                .on_hover_text(t!("shortcut.step_frame"))
+                .clicked()
+            {
+                let fps = info.frame_rate.filter(|fps| *fps > 0.0).unwrap_or(25.0);
+                self.playhead.step(1.0 / fps, duration);
+            }
+            if ui
+                .button("+1s")
+                .on_hover_text(t!("shortcut.step_second"))
+                .clicked()
+            {
+                self.playhead.step(1.0, duration);
+            }
+            if ui.button(t!("playhead.mark_in")).clicked() {
+                self.mark_at_playhead(ctx, true);
+            }
+            if ui.button(t!("playhead.mark_out")).clicked() {
+                self.mark_at_playhead(ctx, false);
+            }
+            if ui.button(t!("shortcut.title")).clicked() {
+                self.show_shortcuts = !self.show_shortcuts;
+            }
+        });
+
+        // 取回后台截取的画面
+        if let Ok(mut frame) = self.playhead_frame.try_lock()
+            && let Some((generation, img_data)) = frame.take()
+            && generation == self.playhead_generation
+        {
+            if let Some(image) = load_image(&img_data) {
+                self.playhead_texture =
+                    Some(ctx.load_texture("playhead", image, egui::TextureOptions::LINEAR));
+            }
+            self.playhead_loading = false;
+        }
+        self.request_playhead_frame(ctx);
+
+        if let Some(texture) = &self.playhead_texture {
+            let size = texture.size_vec2();
+            let scale = (600.0 / size.x).min(300.0 / size.y).min(1.0);
+            ui.image(texture, size * scale);
+        } else if self.playhead_loading {
+            ui.spinner();
+        }
+    }
+
+    // 快捷键帮助窗口, F1 打开, Esc 关闭
+    fn shortcut_help(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_shortcuts;
+        egui::Window::new(t!("shortcut.title"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcut_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (keys, description) in shortcuts::HELP {
+                            ui.monospace(*keys);
+                            ui.label(t!(description));
+                            ui.end_row();
+                        }
+                    });
+                ui.weak(t!("shortcut.typing_note"));
+            });
+        self.show_shortcuts = open;
     }
 
     // 在UI布局中增加预览面板
     fn preview_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        self.playhead_panel(ui, ctx);
+        if self.selected_index().is_some() {
+            ui.separator();
+        }
+
         // 开始时间预览部分
         ui.vertical(|ui| {
             ui.horizontal(|ui| {
-                ui.label("开始时间预览 (HH:MM:SS):");
+                ui.label(t!("ui.start_preview"));
                 ui.text_edit_singleline(&mut self.start_preview_time);
 
                 // 生成预览按钮
-                if ui.button("🔄 生成预览").clicked() {
+                if ui.button(t!("ui.generate_preview")).clicked() {
                     self.generate_preview(ctx, true);
                 }
             });
@@ -351,7 +897,7 @@ impl VideoProcessor {
             if self.start_preview_loading {
                 ui.horizontal(|ui| {
                     ui.spinner();
-                    ui.label("正在生成开始时间预览...");
+                    ui.label(t!("ui.start_preview_loading"));
                 });
             }
 
@@ -378,11 +924,11 @@ impl VideoProcessor {
         // 结束时间预览部分
         ui.vertical(|ui| {
             ui.horizontal(|ui| {
-                ui.label("结束时间预览 (HH:MM:SS):");
+                ui.label(t!("ui.end_preview"));
                 ui.text_edit_singleline(&mut self.end_preview_time);
 
                 // 生成预览按钮
-                if ui.button("🔄 生成预览").clicked() {
+                if ui.button(t!("ui.generate_preview")).clicked() {
                     self.generate_preview(ctx, false);
                 }
             });
@@ -391,7 +937,7 @@ impl VideoProcessor {
             if self.end_preview_loading {
                 ui.horizontal(|ui| {
                     ui.spinner();
-                    ui.label("正在生成结束时间预览...");
+                    ui.label(t!("ui.end_preview_loading"));
                 });
             }
 
@@ -413,422 +959,2077 @@ impl VideoProcessor {
         });
 
         // 异步更新纹理 - 只在有新帧数据时更新
-        if let Ok(mut frame) = self.current_start_preview_frame.try_lock() {
-            if let Some(img_data) = frame.take() {
-                if let Some(image) = load_image(&img_data) {
-                    self.start_preview_texture = Some(ctx.load_texture(
-                        "start_preview",
-                        image,
-                        egui::TextureOptions::LINEAR,
-                    ));
-                    ctx.request_repaint();
-                }
-                self.start_preview_loading = false;
+        if let Ok(mut frame) = self.current_start_preview_frame.try_lock()
+            && let Some(img_data) = frame.take()
+        {
+            if let Some(image) = load_image(&img_data) {
+                self.start_preview_texture =
+                    Some(ctx.load_texture("start_preview", image, egui::TextureOptions::LINEAR));
+                ctx.request_repaint();
             }
+            self.start_preview_loading = false;
         }
 
-        if let Ok(mut frame) = self.current_end_preview_frame.try_lock() {
-            if let Some(img_data) = frame.take() {
-                if let Some(image) = load_image(&img_data) {
-                    self.end_preview_texture =
-                        Some(ctx.load_texture("end_preview", image, egui::TextureOptions::LINEAR));
-                    ctx.request_repaint();
-                }
-                self.end_preview_loading = false;
+        if let Ok(mut frame) = self.current_end_preview_frame.try_lock()
+            && let Some(img_data) = frame.take()
+        {
+            if let Some(image) = load_image(&img_data) {
+                self.end_preview_texture =
+                    Some(ctx.load_texture("end_preview", image, egui::TextureOptions::LINEAR));
+                ctx.request_repaint();
             }
+            self.end_preview_loading = false;
         }
     }
 
-    fn handle_file_drop(&mut self, ctx: &egui::Context) {
-        let dropped_files = ctx.input(|i| i.raw.dropped_files.clone());
-        for file in &dropped_files {
-            if let Some(path) = &file.path {
-                let path_str = path.display().to_string();
-                if !self.source_paths.contains(&path_str) {
-                    self.source_paths.push(path_str.clone());
-                    let (duration, size, format) = get_video_info(&path_str);
-                    self.video_duration = duration;
-                    self.video_size = size;
-                    self.video_format = format;
-                }
-            }
-        }
+    // 当前选中文件的下标(越界时视为未选中)
+    fn selected_index(&self) -> Option<usize> {
+        self.selected.filter(|&i| i < self.sources.len())
     }
 
-    fn file_management_panel(&mut self, ui: &mut egui::Ui) {
-        // 顶部固定区域
-        ui.horizontal(|ui| {
-            ui.vertical(|ui| {
-                ui.horizontal(|ui| {
-                    ui.label("已选文件:");
-                    ui.label(format!("{} 个文件", self.source_paths.len()));
-                });
-                if ui.button("清空列表").clicked() {
-                    self.source_paths.clear();
-                    self.clear_previews(); // 新增清空预览方法
-                }
-            });
-        });
-        egui::ScrollArea::both()
-            .max_height(100.0) // Fixed height scroll area
-            .show(ui, |ui| {
-                egui::Grid::new("file_grid").num_columns(3).show(ui, |ui| {
-                    let mut paths_to_remove = Vec::new();
-                    for path in &self.source_paths {
-                        // ui.label(Path::new(path).file_name().unwrap().to_str().unwrap());
-                        ui.label(path);
-                        if ui.button("移除").clicked() {
-                            paths_to_remove.push(path.clone());
-                        }
-                        ui.end_row();
-                    }
-                    self.source_paths.retain(|p| !paths_to_remove.contains(p));
-                });
-            });
+    // 参数面板当前编辑的参数: 选中文件的参数, 未选中时为默认参数
+    fn current_params(&self) -> &TaskParams {
+        match self.selected_index() {
+            Some(i) => &self.sources[i].params,
+            None => &self.default_params,
+        }
     }
 
-    fn video_info_panel(&self, ui: &mut egui::Ui) {
-        if self.source_paths.is_empty() {
-            ui.label("尚未选择任何视频文件。");
-        } else {
-            ui.heading("视频基本信息");
-            ui.label(format!("视频长度: {}", self.video_duration));
-            ui.label(format!("视频大小: {}", self.video_size));
-            ui.label(format!("视频格式: {}", self.video_format));
+    fn set_current_params(&mut self, params: TaskParams) {
+        match self.selected_index() {
+            Some(i) => self.sources[i].params = params,
+            None => self.default_params = params,
         }
     }
 
-    fn settings_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
-        ui.heading("参数设置");
-        // 当start_time改变时，如果start_preview_time未被手动修改过，则同步更新start_preview_time
-        let old_start_time = self.start_time.clone();
-        let old_end_time = self.end_time.clone();
-        let old_start_preview_time = self.start_preview_time.clone();
-        let old_end_preview_time = self.end_preview_time.clone();
-        let old_rotation = self.rotation.clone();
-
-        // 输出目录
-        ui.horizontal(|ui| {
-            ui.label("输出目录:");
-            ui.text_edit_singleline(&mut self.output_dir);
-            if ui.button("选择...").clicked() {
-                if let Some(dir) = rfd::FileDialog::new().pick_folder() {
-                    self.output_dir = dir.display().to_string();
-                    self.save_config();
-                }
-            }
-        });
-
-        // 文件名模板
-        ui.horizontal(|ui| {
-            ui.label("文件名模板:");
-            ui.text_edit_singleline(&mut self.output_template);
-            if ui.button("重置").clicked() {
-                self.output_template = "{input_name}_processed_{rotation}_{timestamp}".to_string();
-            }
-        });
-        ui.label("可用变量: {input_name} {rotation} {timestamp} {date} {time}");
+    // 选中文件并刷新视频信息
+    fn select_file(&mut self, index: usize) {
+        self.selected = Some(index);
+        let (duration, size, format) = get_video_info(&self.sources[index].path);
+        self.video_duration = duration;
+        self.video_size = size;
+        self.video_format = format;
+        self.reset_playhead();
+    }
 
-        // 时间参数
-        ui.horizontal(|ui| {
-            ui.label("开始时间:");
-            ui.text_edit_singleline(&mut self.start_time);
-            ui.label("结束时间:");
-            ui.text_edit_singleline(&mut self.end_time);
-        });
+    fn reset_playhead(&mut self) {
+        self.playhead.reset();
+        self.playhead_texture = None;
+        self.playhead_frame_time = None;
+        // 旧文件的截取仍在进行时, 结果到达后按代数丢弃
+        self.playhead_generation += 1;
+        self.playhead_loading = false;
+    }
 
-        // 旋转参数
-        ui.horizontal(|ui| {
-            ui.label("旋转角度:");
-            egui::ComboBox::from_id_source("rotation")
-                .selected_text(format!("{}°", self.rotation))
-                .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.rotation, 0, "0°");
-                    ui.selectable_value(&mut self.rotation, 90, "90°");
-                    ui.selectable_value(&mut self.rotation, 180, "180°");
-                    ui.selectable_value(&mut self.rotation, 270, "270°");
-                });
-        });
+    // 选中文件的时长, 用于限制播放头位置
+    fn selected_duration(&self) -> Option<f64> {
+        let i = self.selected_index()?;
+        media::info(&self.sources[i].path).duration
+    }
 
-        // 如果start_time或rotation被修改且start_preview_time未被手动修改过，则同步更新start_preview_time并生成预览
-        if (self.start_time != old_start_time || self.rotation != old_rotation)
-            && self.start_preview_time == old_start_preview_time
-        {
-            self.start_preview_time = self.start_time.clone();
-            self.generate_preview(ctx, true);
+    // 移除文件并调整选中项
+    fn remove_source(&mut self, i: usize) {
+        self.sources.remove(i);
+        self.selected = match self.selected {
+            Some(s) if s == i => None,
+            Some(s) if s > i => Some(s - 1),
+            other => other,
+        };
+        if self.selected.is_none() {
+            self.reset_playhead();
         }
+    }
 
-        // 如果end_time或rotation被修改且end_preview_time未被手动修改过，则同步更新end_preview_time并生成预览
-        if (self.end_time != old_end_time || self.rotation != old_rotation)
-            && self.end_preview_time == old_end_preview_time
-        {
-            self.end_preview_time = self.end_time.clone();
-            self.generate_preview(ctx, false);
+    fn handle_file_drop(&mut self, ctx: &egui::Context) {
+        let dropped: Vec<PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|f| f.path.clone())
+                .collect()
+        });
+        if !dropped.is_empty() {
+            self.add_paths(ctx, dropped);
         }
+        self.receive_imports(ctx);
+        self.receive_duplicates(ctx);
     }
 
-    fn process_control(&mut self, ui: &mut egui::Ui) {
-        ui.horizontal(|ui| {
-            // 通过块作用域限制锁的生命周期
-            let processing = {
-                let lock = self.processing.lock().unwrap();
-                *lock
-            };
-            // 开始处理按钮
-            if ui
-                .add_enabled(!processing, egui::Button::new("开始处理"))
-                .clicked()
-            {
-                self.prepare_batch_tasks();
-                let state = self.state.clone();
-                let tasks = self.batch_queue.clone();
-                let processing_flag = self.processing.clone();
-
-                // 启动处理线程
-                std::thread::spawn(move || {
-                    *processing_flag.lock().unwrap() = true;
-                    for task in tasks {
-                        *state.message.lock().unwrap() = format!("处理中: {}", task.input_path);
-                        if let Err(e) = process_task(task, &state) {
-                            *state.message.lock().unwrap() = format!("错误: {}", e);
-                            break;
-                        }
-                    }
-                    // 处理完成后更新状态
-                    *state.message.lock().unwrap() = "处理完成".to_string();
-
-                    *state.progress.lock().unwrap() = 0.0;
-                    *processing_flag.lock().unwrap() = false; // 关键修改点
-                });
+    // 在后台展开文件夹并用 ffprobe 检查, 大量文件时不阻塞界面
+    fn add_paths(&mut self, ctx: &egui::Context, paths: Vec<PathBuf>) {
+        let settings = self.import.clone();
+        let existing: Vec<String> = self.sources.iter().map(|e| e.path.clone()).collect();
+        let results = self.import_results.clone();
+        let ctx = ctx.clone();
+        self.imports_running += 1;
+        std::thread::spawn(move || {
+            let collected = import::collect(&paths, &settings, &existing);
+            // 预先读取文件信息, 避免文件列表首次显示时逐个调用 ffprobe
+            for file in &collected.files {
+                media::info(&file.display().to_string());
             }
+            results.lock().unwrap().push(collected);
+            ctx.request_repaint();
+        });
+    }
 
-            if ui.button("停止").clicked() {
-                *self.processing.lock().unwrap() = false;
+    // 把后台检查通过的文件加入列表
+    fn receive_imports(&mut self, ctx: &egui::Context) {
+        let finished = std::mem::take(&mut *self.import_results.lock().unwrap());
+        if finished.is_empty() {
+            return;
+        }
+        self.imports_running = self.imports_running.saturating_sub(finished.len());
+        self.skipped_files.clear();
+        let mut added = 0;
+        for collected in finished {
+            for path in collected.files {
+                let path = path.display().to_string();
+                // 后台检查期间可能已通过其他方式加入
+                if self.sources.iter().any(|e| e.path == path) {
+                    self.skipped_files.push(import::Skipped {
+                        path: PathBuf::from(path),
+                        reason: t!("import.duplicate").to_string(),
+                    });
+                    continue;
+                }
+                // 新文件使用当前默认参数
+                self.sources.push(SourceEntry {
+                    path,
+                    params: self.default_params.clone(),
+                    checked: false,
+                });
+                added += 1;
             }
-        });
+            self.skipped_files.extend(collected.skipped);
+        }
+        if added > 0 {
+            self.select_file(self.sources.len() - 1);
+            self.check_duplicates(ctx);
+        }
+        *self.state.message.lock().unwrap() = t!("import.summary", added, self.skipped_files.len());
     }
 
-    fn progress_display(&self, ui: &mut egui::Ui) {
-        let progress = *self.state.progress.lock().unwrap();
-        ui.add(egui::ProgressBar::new(progress).text(format!("进度: {:.1}%", progress * 100.0)));
+    // 在后台比较列表中文件的内容
+    fn check_duplicates(&mut self, ctx: &egui::Context) {
+        if self.duplicate_scanning {
+            self.duplicate_rescan = true;
+            return;
+        }
+        self.duplicate_scanning = true;
+        let paths: Vec<String> = self.sources.iter().map(|e| e.path.clone()).collect();
+        let settings = self.duplicate_settings.clone();
+        let results = self.duplicate_results.clone();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let groups = duplicates::find(&paths, &settings);
+            *results.lock().unwrap() = Some(groups);
+            ctx.request_repaint();
+        });
+    }
+
+    fn receive_duplicates(&mut self, ctx: &egui::Context) {
+        let Some(groups) = self.duplicate_results.lock().unwrap().take() else {
+            return;
+        };
+        self.duplicate_scanning = false;
+        self.duplicate_groups = groups;
+        // 检测期间加入的文件还没有比较过
+        if std::mem::take(&mut self.duplicate_rescan) {
+            self.check_duplicates(ctx);
+            return;
+        }
+        for group in &self.duplicate_groups {
+            logging::log(t!("duplicate.found", group.paths.join(", ")));
+        }
+    }
+
+    // 文件所在的重复组中仍在列表里的其他文件
+    fn duplicates_of(&self, path: &str) -> Option<(MatchKind, Vec<&str>)> {
+        let group = self
+            .duplicate_groups
+            .iter()
+            .find(|g| g.paths.iter().any(|p| p == path))?;
+        let others: Vec<&str> = group
+            .paths
+            .iter()
+            .filter(|p| *p != path && self.sources.iter().any(|e| e.path == **p))
+            .map(String::as_str)
+            .collect();
+        (!others.is_empty()).then_some((group.kind, others))
+    }
+
+    // 多选每组重复文件中除第一个以外的文件, 便于一并移除
+    fn check_duplicate_copies(&mut self) {
+        let mut seen: Vec<usize> = Vec::new();
+        for entry in &mut self.sources {
+            entry.checked = false;
+        }
+        for i in 0..self.sources.len() {
+            let path = &self.sources[i].path;
+            let Some(g) = self
+                .duplicate_groups
+                .iter()
+                .position(|g| g.paths.contains(path))
+            else {
+                continue;
+            };
+            if seen.contains(&g) {
+                self.sources[i].checked = true;
+            } else {
+                seen.push(g);
+            }
+        }
+    }
+
+    fn duplicate_panel(&mut self, ui: &mut egui::Ui) {
+        let old = self.duplicate_settings.clone();
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(
+                    !self.duplicate_scanning && self.sources.len() > 1,
+                    egui::Button::new(t!("duplicate.check")),
+                )
+                .clicked()
+            {
+                self.check_duplicates(ui.ctx());
+            }
+            ui.checkbox(
+                &mut self.duplicate_settings.perceptual,
+                t!("duplicate.perceptual"),
+            )
+            .on_hover_text(t!("duplicate.perceptual_hint"));
+            ui.add_enabled_ui(self.duplicate_settings.perceptual, |ui| {
+                ui.label(t!("duplicate.threshold"));
+                ui.add(
+                    egui::DragValue::new(&mut self.duplicate_settings.threshold)
+                        .clamp_range(0..=32),
+                );
+            });
+            if self.duplicate_scanning {
+                ui.spinner();
+                ui.label(t!("duplicate.scanning"));
+                return;
+            }
+            let count = self
+                .sources
+                .iter()
+                .filter(|e| self.duplicates_of(&e.path).is_some())
+                .count();
+            if count > 0 {
+                ui.colored_label(
+                    egui::Color32::from_rgb(255, 165, 0),
+                    t!("duplicate.summary", count),
+                );
+                if ui.button(t!("duplicate.select")).clicked() {
+                    self.check_duplicate_copies();
+                }
+            }
+        });
+        if self.duplicate_settings != old {
+            self.save_config();
+        }
+    }
+
+    // 添加文件的过滤设置和最近一次被跳过的文件
+    fn import_panel(&mut self, ui: &mut egui::Ui) {
+        if self.imports_running > 0 {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label(t!("import.scanning"));
+            });
+        }
+
+        let old = self.import.clone();
+        ui.collapsing(t!("import.options"), |ui| {
+            ui.horizontal(|ui| {
+                ui.label(t!("import.extensions"));
+                ui.add(
+                    egui::TextEdit::singleline(&mut self.import.extensions)
+                        .hint_text(t!("import.extensions_hint"))
+                        .desired_width(400.0),
+                );
+                if ui.button(t!("common.reset")).clicked() {
+                    self.import.extensions = ImportSettings::default().extensions;
+                }
+            });
+            ui.checkbox(&mut self.import.validate, t!("import.validate"));
+        });
+        if self.import != old {
+            self.save_config();
+        }
+
+        if self.skipped_files.is_empty() {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.collapsing(t!("import.skipped_count", self.skipped_files.len()), |ui| {
+                egui::ScrollArea::vertical()
+                    .id_source("skipped_files")
+                    .max_height(150.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("skipped_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for skipped in &self.skipped_files {
+                                    ui.label(skipped.path.display().to_string());
+                                    ui.weak(&skipped.reason);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+            if ui.button(t!("import.dismiss")).clicked() {
+                self.skipped_files.clear();
+            }
+        });
+    }
+
+    // 通过对话框添加文件或文件夹
+    fn add_files_dialog(&mut self, ctx: &egui::Context) {
+        let extensions = self.import.extension_list();
+        let extensions: Vec<&str> = extensions.iter().map(String::as_str).collect();
+        let mut dialog = rfd::FileDialog::new();
+        if !extensions.is_empty() {
+            dialog = dialog.add_filter(t!("files.media_filter"), &extensions);
+        }
+        if let Some(paths) = dialog.pick_files() {
+            self.add_paths(ctx, paths);
+        }
+    }
+
+    fn add_folder_dialog(&mut self, ctx: &egui::Context) {
+        if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+            self.add_paths(ctx, vec![dir]);
+        }
+    }
+
+    // 多选的文件, 没有多选时为当前选中的文件
+    fn checked_indices(&self) -> Vec<usize> {
+        let checked: Vec<usize> = (0..self.sources.len())
+            .filter(|&i| self.sources[i].checked)
+            .collect();
+        if checked.is_empty() {
+            self.selected_index().into_iter().collect()
+        } else {
+            checked
+        }
+    }
+
+    fn remove_sources(&mut self, mut indices: Vec<usize>) {
+        // 从后往前移除, 前面的下标保持不变
+        indices.sort_unstable();
+        for i in indices.into_iter().rev() {
+            self.remove_source(i);
+        }
+    }
+
+    // 单击只选中该行, Ctrl 单击切换多选, Shift 单击选中一个范围
+    fn click_source(&mut self, index: usize, modifiers: egui::Modifiers) {
+        if modifiers.command {
+            // 从单选切换到多选时保留原来选中的文件
+            if !self.sources.iter().any(|e| e.checked)
+                && let Some(current) = self.selected_index()
+            {
+                self.sources[current].checked = true;
+            }
+            self.sources[index].checked = !self.sources[index].checked;
+        } else if modifiers.shift
+            && let Some(anchor) = self.selected_index()
+        {
+            let range = anchor.min(index)..=anchor.max(index);
+            for (i, entry) in self.sources.iter_mut().enumerate() {
+                entry.checked = range.contains(&i);
+            }
+        } else {
+            for (i, entry) in self.sources.iter_mut().enumerate() {
+                entry.checked = i == index;
+            }
+        }
+        if !modifiers.shift {
+            self.select_file(index);
+        }
+    }
+
+    // 调整文件顺序, 选中项跟随移动
+    fn move_source(&mut self, from: usize, to: usize) {
+        let entry = self.sources.remove(from);
+        self.sources.insert(to, entry);
+        self.selected = self.selected.map(|s| file_list::moved_index(s, from, to));
+        self.sort = None;
+    }
+
+    fn sort_sources(&mut self, ctx: &egui::Context, column: SortColumn) {
+        let ascending = self.sort != Some((column, true));
+        self.refresh_file_rows(ctx);
+        let rows = self.file_rows.rows();
+        let mut order: Vec<usize> = (0..self.sources.len()).collect();
+        order.sort_by(|&a, &b| {
+            let ordering = file_list::compare(&rows[a], &rows[b], column);
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+        let mut old: Vec<Option<SourceEntry>> = self.sources.drain(..).map(Some).collect();
+        self.sources = order.iter().map(|&i| old[i].take().unwrap()).collect();
+        self.selected = self
+            .selected
+            .and_then(|s| order.iter().position(|&i| i == s));
+        self.sort = Some((column, ascending));
+    }
+
+    // 最近一次处理中各文件的结果
+    fn source_statuses(&self, results: &[TaskStatus]) -> Vec<Option<TaskStatus>> {
+        let mut first_task = HashMap::new();
+        for (i, task) in self.batch_queue.iter().enumerate().rev() {
+            first_task.insert(task.input_path.as_str(), i);
+        }
+        self.sources
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                // 调整顺序后下标可能不再对应, 按路径查找
+                let task = match self.batch_queue.get(index) {
+                    Some(task) if task.input_path == entry.path => index,
+                    _ => *first_task.get(entry.path.as_str())?,
+                };
+                results.get(task).copied()
+            })
+            .collect()
+    }
+
+    // 文件列表或处理结果变化时更新表格的缓存行
+    fn refresh_file_rows(&mut self, ctx: &egui::Context) {
+        let results: Vec<TaskStatus> = self
+            .state
+            .results
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| r.status)
+            .collect();
+        let paths: Vec<&str> = self.sources.iter().map(|e| e.path.as_str()).collect();
+        if self.file_rows.update(ctx, &paths, &results) {
+            let statuses = self.source_statuses(&results);
+            self.file_rows.set_statuses(statuses);
+        }
+    }
+
+    fn file_management_panel(&mut self, ui: &mut egui::Ui) {
+        // 顶部固定区域
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.selected_files"));
+            ui.label(t!("ui.file_count", self.sources.len()));
+            let checked = self.sources.iter().filter(|e| e.checked).count();
+            if checked > 1 {
+                ui.label(t!("files.checked_count", checked));
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui.button(t!("files.add_files")).clicked() {
+                self.add_files_dialog(ui.ctx());
+            }
+            if ui.button(t!("files.add_folder")).clicked() {
+                self.add_folder_dialog(ui.ctx());
+            }
+            let checked = self.checked_indices();
+            if ui
+                .add_enabled(
+                    !checked.is_empty(),
+                    egui::Button::new(t!("files.remove_checked")),
+                )
+                .clicked()
+            {
+                self.remove_sources(checked.clone());
+            }
+            // 将当前文件的参数复制到其余多选的文件
+            let selected = self.selected_index();
+            if ui
+                .add_enabled(
+                    selected.is_some() && checked.iter().any(|&i| Some(i) != selected),
+                    egui::Button::new(t!("ui.copy_params")),
+                )
+                .clicked()
+                && let Some(from) = selected
+            {
+                let params = self.sources[from].params.clone();
+                for i in checked {
+                    self.sources[i].params = params.clone();
+                }
+            }
+            if ui.button(t!("ui.clear_list")).clicked() {
+                self.sources.clear();
+                self.selected = None;
+                self.clear_previews(); // 新增清空预览方法
+            }
+            if ui.button(t!("project.open")).clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter(t!("project.filter"), &["json"])
+                    .pick_file()
+            {
+                self.open_project(&path);
+            }
+            if ui.button(t!("project.save")).clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter(t!("project.filter"), &["json"])
+                    .set_file_name("project.json")
+                    .save_file()
+            {
+                self.save_project(&path);
+            }
+        });
+        self.duplicate_panel(ui);
+        self.file_table(ui);
+    }
+
+    // 文件表格: 点击表头排序, 拖动 ☰ 调整处理顺序
+    fn file_table(&mut self, ui: &mut egui::Ui) {
+        use egui_extras::{Column, TableBuilder};
+
+        self.refresh_file_rows(ui.ctx());
+        let selected = self.selected_index();
+        let modifiers = ui.input(|i| i.modifiers);
+        let mut clicked_header = None;
+        let mut clicked_row = None;
+        let mut drag_started = None;
+        let mut row_ranges = Vec::new(); // (下标, 行的上下边界)
+
+        ui.scope(|ui| {
+            // 主区域的行距较大, 表格内恢复紧凑的行距
+            ui.spacing_mut().item_spacing.y = 4.0;
+            TableBuilder::new(ui)
+                .striped(true)
+                .resizable(true)
+                .max_scroll_height(250.0)
+                .column(Column::exact(20.0))
+                .column(Column::initial(260.0).at_least(80.0).clip(true))
+                .columns(Column::auto().at_least(60.0), 4)
+                .column(Column::remainder().at_least(60.0))
+                .header(22.0, |mut header| {
+                    header.col(|_| {});
+                    for column in SortColumn::ALL {
+                        header.col(|ui| {
+                            let arrow = match self.sort {
+                                Some((c, true)) if c == column => " ⬆",
+                                Some((c, false)) if c == column => " ⬇",
+                                _ => "",
+                            };
+                            let label = format!("{}{}", column.label(), arrow);
+                            if ui.selectable_label(false, label).clicked() {
+                                clicked_header = Some(column);
+                            }
+                        });
+                    }
+                })
+                .body(|body| {
+                    body.rows(20.0, self.sources.len(), |i, mut row| {
+                        let entry = &self.sources[i];
+                        let info = &self.file_rows.rows()[i];
+                        let (_, handle) = row.col(|ui| {
+                            let response = ui
+                                .add(egui::Label::new("☰").sense(egui::Sense::drag()))
+                                .on_hover_text(t!("files.drag_hint"));
+                            if response.drag_started() {
+                                drag_started = Some(i);
+                            }
+                        });
+                        row_ranges.push((i, handle.rect.top(), handle.rect.bottom()));
+                        row.col(|ui| {
+                            let duplicates = self.duplicates_of(&entry.path);
+                            let text = match &duplicates {
+                                _ if info.missing() => {
+                                    egui::RichText::new(&info.name).color(egui::Color32::RED)
+                                }
+                                Some((MatchKind::Identical, _)) => {
+                                    egui::RichText::new(format!("⚠ {}", info.name))
+                                        .color(egui::Color32::from_rgb(255, 165, 0))
+                                }
+                                Some((MatchKind::Similar, _)) => {
+                                    egui::RichText::new(format!("≈ {}", info.name))
+                                        .color(egui::Color32::YELLOW)
+                                }
+                                None => egui::RichText::new(&info.name),
+                            };
+                            let hover = match &duplicates {
+                                Some((kind, others)) => {
+                                    let key = match kind {
+                                        MatchKind::Identical => "duplicate.identical_to",
+                                        MatchKind::Similar => "duplicate.similar_to",
+                                    };
+                                    format!("{}\n{}", entry.path, t!(key, others.join("\n")))
+                                }
+                                None => entry.path.clone(),
+                            };
+                            let highlighted = entry.checked || selected == Some(i);
+                            if ui
+                                .selectable_label(highlighted, text)
+                                .on_hover_text(hover)
+                                .clicked()
+                            {
+                                clicked_row = Some(i);
+                            }
+                        });
+                        row.col(|ui| {
+                            ui.label(info.duration_text());
+                        });
+                        row.col(|ui| {
+                            ui.label(info.resolution_text());
+                        });
+                        row.col(|ui| {
+                            ui.label(info.codec_text());
+                        });
+                        row.col(|ui| {
+                            ui.label(info.size_text());
+                        });
+                        row.col(|ui| {
+                            let color = info.status.map_or(egui::Color32::RED, status_color);
+                            ui.colored_label(color, info.status_text());
+                        });
+                    });
+                });
+        });
+
+        if let Some(column) = clicked_header {
+            self.sort_sources(ui.ctx(), column);
+        }
+        if let Some(i) = clicked_row {
+            self.click_source(i, modifiers);
+        }
+        if drag_started.is_some() {
+            self.dragging_source = drag_started;
+        }
+
+        // 拖动经过其他行时把文件移到该位置
+        let Some(from) = self.dragging_source else {
+            return;
+        };
+        let (down, pointer) = ui.input(|i| (i.pointer.primary_down(), i.pointer.interact_pos()));
+        if !down {
+            self.dragging_source = None;
+            return;
+        }
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+        if let Some(pos) = pointer
+            && let Some(&(to, _, _)) = row_ranges
+                .iter()
+                .find(|(i, top, bottom)| *i != from && (*top..=*bottom).contains(&pos.y))
+            && from < self.sources.len()
+        {
+            self.move_source(from, to);
+            self.dragging_source = Some(to);
+        }
+    }
+
+    fn save_project(&self, path: &Path) {
+        let project = project::Project {
+            sources: self.sources.clone(),
+            default_params: self.default_params.clone(),
+            output_dir: self.output_dir.clone(),
+            output_template: self.output_template.clone(),
+            global_args: self.global_args.clone(),
+            batch_queue: self.batch_queue.clone(),
+            sanitize: Some(self.sanitize.clone()),
+            collision_policy: Some(self.collision_policy),
+            log_dir: Some(self.log_dir.clone()),
+            verify: Some(self.verify.clone()),
+            ..Default::default()
+        };
+        *self.state.message.lock().unwrap() = match project::save_project(path, &project) {
+            Ok(()) => t!("project.saved", path.display()),
+            Err(e) => t!("common.error", e),
+        };
+    }
+
+    fn open_project(&mut self, path: &Path) {
+        let project = match project::load_project(path) {
+            Ok(project) => project,
+            Err(e) => {
+                *self.state.message.lock().unwrap() = t!("common.error", e);
+                return;
+            }
+        };
+
+        self.sources = project.sources;
+        self.default_params = project.default_params;
+        self.output_dir = project.output_dir;
+        self.output_template = project.output_template;
+        self.global_args = project.global_args;
+        self.batch_queue = project.batch_queue;
+        if let Some(sanitize) = project.sanitize {
+            self.sanitize = sanitize;
+        }
+        if let Some(policy) = project.collision_policy {
+            self.collision_policy = policy;
+        }
+        if let Some(log_dir) = project.log_dir {
+            self.log_dir = log_dir;
+        }
+        if let Some(verify) = project.verify {
+            self.verify = verify;
+        }
+        self.file_rows.invalidate_statuses();
+        self.selected = None;
+        self.clear_previews();
+        if !self.sources.is_empty() {
+            self.select_file(0);
+        }
+
+        // 提示已不存在的源文件
+        let missing = self
+            .sources
+            .iter()
+            .filter(|e| !Path::new(&e.path).exists())
+            .count();
+        *self.state.message.lock().unwrap() = if missing > 0 {
+            t!("project.opened_missing", missing)
+        } else {
+            t!("project.opened", path.display())
+        };
+    }
+
+    fn video_info_panel(&self, ui: &mut egui::Ui) {
+        if self.sources.is_empty() {
+            ui.label(t!("ui.no_file"));
+        } else {
+            ui.heading(t!("ui.video_info"));
+            ui.label(t!("ui.video_duration", self.video_duration));
+            ui.label(t!("ui.video_size", self.video_size));
+            ui.label(t!("ui.video_format", self.video_format));
+            if let Some(i) = self.selected_index() {
+                let info = media::info(&self.sources[i].path);
+                if let Some(fps) = info.frame_rate {
+                    ui.label(t!("ui.frame_rate", format!("{:.3}", fps)));
+                }
+                if let Some(timecode) = &info.start_timecode {
+                    ui.label(t!("ui.start_timecode", timecode));
+                }
+            }
+        }
+    }
+
+    fn settings_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.heading(t!("ui.settings"));
+        // 在副本上编辑当前参数, 结束时写回
+        let old_params = self.current_params().clone();
+        let mut params = old_params.clone();
+        // 当start_time改变时，如果start_preview_time未被手动修改过，则同步更新start_preview_time
+        let old_start_preview_time = self.start_preview_time.clone();
+        let old_end_preview_time = self.end_preview_time.clone();
+
+        // 预设
+        self.preset_panel(ui, &mut params);
+
+        // 输出目录
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.output_dir"));
+            ui.text_edit_singleline(&mut self.output_dir);
+            if ui.button(t!("common.browse")).clicked()
+                && let Some(dir) = rfd::FileDialog::new().pick_folder()
+            {
+                self.output_dir = dir.display().to_string();
+                self.save_config();
+            }
+        });
+
+        // 文件名模板
+        ui.horizontal(|ui| {
+            ui.label(t!("template.label"));
+            ui.text_edit_singleline(&mut self.output_template);
+            if ui.button(t!("common.reset")).clicked() {
+                self.output_template = DEFAULT_TEMPLATE.to_string();
+            }
+        });
+        self.template_help(ui);
+        self.sanitize_panel(ui);
+
+        // 当前编辑对象
+        ui.horizontal(|ui| {
+            match self.selected_index() {
+                Some(i) => ui.label(t!("ui.current_file", self.sources[i].path)),
+                None => ui.label(t!("ui.editing_defaults")),
+            };
+            if ui
+                .add_enabled(
+                    !self.sources.is_empty(),
+                    egui::Button::new(t!("ui.apply_all")),
+                )
+                .clicked()
+            {
+                for entry in &mut self.sources {
+                    entry.params = params.clone();
+                }
+                self.default_params = params.clone();
+            }
+        });
+
+        // 时间参数
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.start_time"));
+            ui.text_edit_singleline(&mut params.start_time);
+            ui.label(t!("ui.end_time"));
+            ui.add(egui::TextEdit::singleline(&mut params.end_time).hint_text(t!("time.hint")));
+        });
+        // 选中文件时按帧率和起始时间码显示对应的时间码
+        match self.selected_index() {
+            Some(i) => {
+                let info = media::info(&self.sources[i].path);
+                match timecode::trim_range(&params.start_time, &params.end_time, &info) {
+                    Ok(trim) => {
+                        let start = timecode::format_timecode(trim.start.unwrap_or(0.0), &info);
+                        let end = trim
+                            .end
+                            .or(info.duration)
+                            .and_then(|end| timecode::format_timecode(end, &info));
+                        if let (Some(start), Some(end)) = (start, end) {
+                            ui.weak(t!("time.timecode_range", start, end));
+                        }
+                    }
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                }
+            }
+            None => {
+                if let Err(e) = timecode::check_syntax(&params.start_time, &params.end_time) {
+                    ui.colored_label(egui::Color32::RED, e);
+                }
+            }
+        }
+
+        // 旋转参数
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.rotation"));
+            egui::ComboBox::from_id_source("rotation")
+                .selected_text(format!("{}°", params.rotation))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut params.rotation, 0, "0°");
+                    ui.selectable_value(&mut params.rotation, 90, "90°");
+                    ui.selectable_value(&mut params.rotation, 180, "180°");
+                    ui.selectable_value(&mut params.rotation, 270, "270°");
+                });
+        });
+
+        // 裁剪方式
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.trim_mode"));
+            egui::ComboBox::from_id_source("trim_mode")
+                .selected_text(params.trim_mode.label())
+                .show_ui(ui, |ui| {
+                    for mode in [TrimMode::Accurate, TrimMode::Fast] {
+                        ui.selectable_value(&mut params.trim_mode, mode, mode.label());
+                    }
+                });
+        });
+
+        // 变速和倒放
+        speed_settings_ui(ui, &mut params.speed);
+
+        // 编码参数
+        encoding_settings_ui(ui, &mut params.encoding);
+        if let Some(i) = self.selected_index() {
+            for problem in container::check(&self.plan_task(i)) {
+                ui.colored_label(egui::Color32::RED, t!("container.fix_hint", problem));
+            }
+        }
+
+        // 追加参数
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.extra_input"));
+            ui.text_edit_singleline(&mut params.extra_args.input);
+            ui.label(t!("ui.extra_output"));
+            ui.text_edit_singleline(&mut params.extra_args.output);
+        });
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.global_input"));
+            let input = ui.text_edit_singleline(&mut self.global_args.input);
+            ui.label(t!("ui.global_output"));
+            let output = ui.text_edit_singleline(&mut self.global_args.output);
+            if input.lost_focus() || output.lost_focus() {
+                self.save_config();
+            }
+        });
+
+        // 日志目录
+        ui.horizontal(|ui| {
+            ui.label(t!("ui.log_dir"));
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut self.log_dir).hint_text(t!("ui.log_dir_hint")),
+            );
+            if response.lost_focus() {
+                self.save_config();
+            }
+            if ui.button(t!("common.browse")).clicked()
+                && let Some(dir) = rfd::FileDialog::new().pick_folder()
+            {
+                self.log_dir = dir.display().to_string();
+                self.save_config();
+            }
+        });
+        self.font_panel(ui, ctx);
+
+        if params == old_params {
+            return;
+        }
+        self.set_current_params(params.clone());
+
+        // 如果start_time或rotation被修改且start_preview_time未被手动修改过，则同步更新start_preview_time并生成预览
+        if (params.start_time != old_params.start_time || params.rotation != old_params.rotation)
+            && self.start_preview_time == old_start_preview_time
+        {
+            self.start_preview_time = params.start_time.clone();
+            self.generate_preview(ctx, true);
+        }
+
+        // 如果end_time或rotation被修改且end_preview_time未被手动修改过，则同步更新end_preview_time并生成预览
+        if (params.end_time != old_params.end_time || params.rotation != old_params.rotation)
+            && self.end_preview_time == old_end_preview_time
+        {
+            self.end_preview_time = params.end_time.clone();
+            self.generate_preview(ctx, false);
+        }
+    }
+
+    // 界面字体和字号, 修改后立即生效
+    fn font_panel(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.collapsing(t!("font.title"), |ui| {
+            let mut reload = false;
+            ui.horizontal(|ui| {
+                ui.label(t!("font.path"));
+                let response = ui.add(
+                    egui::TextEdit::singleline(&mut self.font.path).hint_text(t!("font.auto_hint")),
+                );
+                reload |= response.lost_focus();
+                if ui.button(t!("common.browse")).clicked()
+                    && let Some(path) = rfd::FileDialog::new()
+                        .add_filter(t!("font.filter"), &["ttf", "otf", "ttc"])
+                        .pick_file()
+                {
+                    self.font.path = path.display().to_string();
+                    reload = true;
+                }
+                if ui.button(t!("common.reset")).clicked() {
+                    self.font.path.clear();
+                    reload = true;
+                }
+            });
+            match &self.loaded_font {
+                Some(path) => ui.label(t!("font.current", path.display())),
+                None => ui.label(t!("font.default")),
+            };
+
+            let old_size = self.font.size;
+            ui.horizontal(|ui| {
+                ui.label(t!("font.size"));
+                ui.add(egui::Slider::new(
+                    &mut self.font.size,
+                    fonts::MIN_FONT_SIZE..=fonts::MAX_FONT_SIZE,
+                ));
+            });
+
+            if reload {
+                self.loaded_font = fonts::load_fonts(ctx, &self.font);
+            }
+            if self.font.size != old_size {
+                fonts::apply_font_size(ctx, self.font.size);
+            }
+            if reload || self.font.size != old_size {
+                self.save_config();
+            }
+        });
+    }
+
+    // 输出文件名清理规则及预览
+    fn sanitize_panel(&mut self, ui: &mut egui::Ui) {
+        let old = self.sanitize.clone();
+        ui.collapsing(t!("sanitize.title"), |ui| {
+            let rules = &mut self.sanitize;
+            ui.checkbox(&mut rules.keep_unicode, t!("sanitize.keep_unicode"));
+            ui.horizontal(|ui| {
+                ui.label(t!("sanitize.invalid_chars"));
+                for mode in [InvalidCharMode::Replace, InvalidCharMode::Remove] {
+                    ui.radio_value(&mut rules.invalid_chars, mode, mode.label());
+                }
+                ui.add_enabled(
+                    rules.invalid_chars == InvalidCharMode::Replace,
+                    egui::TextEdit::singleline(&mut rules.replacement).desired_width(40.0),
+                );
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("sanitize.whitespace"));
+                for mode in [
+                    WhitespaceMode::Keep,
+                    WhitespaceMode::Underscore,
+                    WhitespaceMode::Remove,
+                ] {
+                    ui.radio_value(&mut rules.whitespace, mode, mode.label());
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(t!("sanitize.max_length"));
+                ui.add(egui::DragValue::new(&mut rules.max_length).clamp_range(0..=255));
+            });
+            ui.checkbox(&mut rules.avoid_reserved, t!("sanitize.avoid_reserved"));
+
+            ui.horizontal(|ui| {
+                ui.label(t!("sanitize.sample"));
+                ui.text_edit_singleline(&mut self.sanitize_sample);
+            });
+            ui.label(t!(
+                "sanitize.result",
+                sanitize::sanitize_filename(&self.sanitize_sample, &self.sanitize)
+            ));
+        });
+
+        if self.sanitize != old {
+            self.save_config();
+        }
+    }
+
+    // 界面语言, 切换后立即生效并保存到配置
+    fn language_selector(&mut self, ui: &mut egui::Ui) {
+        let old_language = self.language;
+        ui.label(t!("ui.language"));
+        egui::ComboBox::from_id_source("language")
+            .selected_text(
+                self.language
+                    .map_or(t!("ui.language_system"), Language::label),
+            )
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut self.language, None, t!("ui.language_system"));
+                for language in Language::ALL {
+                    ui.selectable_value(&mut self.language, Some(language), language.label());
+                }
+            });
+        if self.language != old_language {
+            i18n::set_language(self.language.unwrap_or_else(Language::detect));
+            self.save_config();
+        }
+    }
+
+    // 模板变量说明、校验结果和选中文件的输出预览
+    fn template_help(&self, ui: &mut egui::Ui) {
+        ui.collapsing(t!("template.variables"), |ui| {
+            egui::Grid::new("template_variables").show(ui, |ui| {
+                for (name, description) in template::VARIABLES {
+                    ui.monospace(format!("{{{}}}", name));
+                    ui.label(t!(description));
+                    ui.end_row();
+                }
+            });
+            ui.label(t!("template.help"));
+        });
+
+        if let Err(e) = template::validate(&self.output_template) {
+            ui.colored_label(egui::Color32::RED, t!("template.invalid", e));
+            return;
+        }
+        let preview = match self.selected_index() {
+            Some(i) => self.plan_task(i).output_path,
+            None => {
+                let params = self.current_params();
+                let input = TemplateInput {
+                    input_path: &self.sanitize_sample,
+                    params,
+                    index: 1,
+                    segment: 1,
+                };
+                generate_output_path(
+                    &input,
+                    &self.output_dir,
+                    &self.output_template,
+                    &self.sanitize,
+                )
+            }
+        };
+        ui.label(t!("template.preview", preview));
+    }
+
+    // 预设的选择、新建、保存、重命名和删除
+    fn preset_panel(&mut self, ui: &mut egui::Ui, params: &mut TaskParams) {
+        ui.horizontal(|ui| {
+            ui.label(t!("preset.label"));
+            let selected_name = self
+                .selected_preset
+                .and_then(|i| self.presets.get(i))
+                .map(|p| p.name.clone())
+                .unwrap_or_else(|| t!("preset.none").to_string());
+            let mut chosen = None;
+            egui::ComboBox::from_id_source("preset")
+                .selected_text(selected_name)
+                .show_ui(ui, |ui| {
+                    for (i, preset) in self.presets.iter().enumerate() {
+                        if ui
+                            .selectable_label(self.selected_preset == Some(i), &preset.name)
+                            .clicked()
+                        {
+                            chosen = Some(i);
+                        }
+                    }
+                });
+            // 选择预设后立即应用到当前参数
+            if let Some(i) = chosen {
+                let preset = &self.presets[i];
+                preset.apply_to(params);
+                self.output_template = preset.output_template.clone();
+                self.output_dir = preset.output_dir.clone();
+                self.preset_name_input = preset.name.clone();
+                self.selected_preset = Some(i);
+                self.preset_error.clear();
+            }
+
+            ui.label(t!("preset.name"));
+            ui.text_edit_singleline(&mut self.preset_name_input);
+            let name = self.preset_name_input.trim().to_string();
+
+            if ui.button(t!("preset.new")).clicked() {
+                match preset::validate_preset_name(&self.presets, &name) {
+                    Ok(()) => {
+                        self.presets.push(Preset::capture(
+                            &name,
+                            params,
+                            &self.output_template,
+                            &self.output_dir,
+                        ));
+                        self.selected_preset = Some(self.presets.len() - 1);
+                        self.preset_error.clear();
+                        self.save_config();
+                    }
+                    Err(e) => self.preset_error = e,
+                }
+            }
+
+            let selected = self.selected_preset.filter(|&i| i < self.presets.len());
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new(t!("preset.save")))
+                .clicked()
+                && let Some(i) = selected
+            {
+                let name = self.presets[i].name.clone();
+                self.presets[i] =
+                    Preset::capture(&name, params, &self.output_template, &self.output_dir);
+                self.preset_error.clear();
+                self.save_config();
+            }
+
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new(t!("preset.rename")))
+                .clicked()
+                && let Some(i) = selected
+            {
+                if self.presets[i].name == name {
+                    self.preset_error.clear();
+                } else {
+                    match preset::validate_preset_name(&self.presets, &name) {
+                        Ok(()) => {
+                            self.presets[i].name = name;
+                            self.preset_error.clear();
+                            self.save_config();
+                        }
+                        Err(e) => self.preset_error = e,
+                    }
+                }
+            }
+
+            if ui
+                .add_enabled(selected.is_some(), egui::Button::new(t!("preset.delete")))
+                .clicked()
+                && let Some(i) = selected
+            {
+                self.presets.remove(i);
+                self.selected_preset = None;
+                self.preset_error.clear();
+                self.save_config();
+            }
+        });
 
-        let msg = self.state.message.lock().unwrap().clone();
-        ui.label(msg);
+        if !self.preset_error.is_empty() {
+            ui.colored_label(egui::Color32::RED, &self.preset_error);
+        }
+
+        // HandBrake 预设导入
+        ui.horizontal(|ui| {
+            if ui.button(t!("handbrake.import")).clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter(t!("handbrake.filter"), &["json"])
+                    .pick_file()
+            {
+                self.import_handbrake_presets(&path);
+            }
+            if !self.import_report.is_empty() && ui.button(t!("handbrake.close_report")).clicked() {
+                self.import_report.clear();
+            }
+        });
+        if !self.import_report.is_empty() {
+            egui::ScrollArea::vertical()
+                .id_source("import_report")
+                .max_height(120.0)
+                .show(ui, |ui| {
+                    for line in &self.import_report {
+                        ui.label(line);
+                    }
+                });
+        }
     }
 
-    fn prepare_batch_tasks(&mut self) {
-        self.batch_queue = self
-            .source_paths
+    fn import_handbrake_presets(&mut self, path: &Path) {
+        self.import_report.clear();
+        let imported = match fs::read_to_string(path)
+            .map_err(|e| t!("handbrake.read_failed", e))
+            .and_then(|json| handbrake::import_str(&json))
+        {
+            Ok(imported) => imported,
+            Err(e) => {
+                self.import_report.push(t!("handbrake.import_failed", e));
+                return;
+            }
+        };
+
+        for mut item in imported {
+            item.preset.name = preset::unique_preset_name(&self.presets, &item.preset.name);
+            self.import_report
+                .push(t!("handbrake.imported", item.preset.name));
+            for field in &item.unmapped {
+                self.import_report.push(t!("handbrake.unmapped", field));
+            }
+            if !item.ignored.is_empty() {
+                self.import_report.push(t!(
+                    "handbrake.ignored",
+                    item.ignored.len(),
+                    item.ignored.join(", ")
+                ));
+            }
+            self.presets.push(item.preset);
+        }
+        self.save_config();
+    }
+
+    // 按当前设置生成任务, 每个文件携带自己的处理参数
+    fn plan_task(&self, index: usize) -> BatchTask {
+        let entry = &self.sources[index];
+        // 同一源文件可以多次加入列表, 按出现顺序编号
+        let segment = self.sources[..=index]
             .iter()
-            .map(|input_path| {
-                let (output_path, new_input_path) = generate_output_path(
-                    input_path,
-                    &self.output_dir,
-                    &self.output_template,
-                    self.rotation,
+            .filter(|e| e.path == entry.path)
+            .count();
+        let input = TemplateInput {
+            input_path: &entry.path,
+            params: &entry.params,
+            index: index + 1,
+            segment,
+        };
+        let output_path = generate_output_path(
+            &input,
+            &self.output_dir,
+            &self.output_template,
+            &self.sanitize,
+        );
+        BatchTask {
+            input_path: entry.path.clone(),
+            log_path: logging::log_path_for(&output_path, &self.log_dir),
+            output_path,
+            params: entry.params.clone(),
+            global_args: self.global_args.clone(),
+            output_action: OutputAction::default(),
+        }
+    }
+
+    // 开始处理前检查每个任务能否生成有效的命令
+    fn validate_tasks(&self) -> Result<(), String> {
+        template::validate(&self.output_template).map_err(|e| t!("template.error", e))?;
+        for (i, entry) in self.sources.iter().enumerate() {
+            let task = self.plan_task(i);
+            command::build_ffmpeg_args(&task).map_err(|e| format!("{}: {}", entry.path, e))?;
+            if let Some(problem) = container::check(&task).first() {
+                return Err(format!("{}: {}", entry.path, problem));
+            }
+        }
+        Ok(())
+    }
+
+    // 显示选中文件将要执行的 ffmpeg 命令
+    fn command_preview_panel(&self, ui: &mut egui::Ui) {
+        ui.heading(t!("ui.command_preview"));
+        let Some(i) = self.selected_index() else {
+            ui.label(t!("ui.command_hint"));
+            return;
+        };
+
+        match command::build_ffmpeg_args(&self.plan_task(i)) {
+            Ok(args) => {
+                let line = command::format_command(&args);
+                ui.add(
+                    egui::TextEdit::multiline(&mut line.as_str())
+                        .code_editor()
+                        .desired_rows(2)
+                        .desired_width(f32::INFINITY),
                 );
-                BatchTask {
-                    input_path: new_input_path.clone(),
-                    output_path,
-                    start_time: self.start_time.clone(), // 携带处理参数
-                    end_time: self.end_time.clone(),
-                    rotation: self.rotation,
+                ui.horizontal(|ui| {
+                    if ui.button(t!("ui.copy_command")).clicked() {
+                        ui.output_mut(|o| o.copied_text = line.clone());
+                    }
+                    ui.collapsing(t!("ui.arg_list"), |ui| {
+                        for (n, arg) in args.iter().enumerate() {
+                            ui.monospace(format!("[{}] {}", n, arg));
+                        }
+                    });
+                });
+            }
+            Err(e) => {
+                ui.colored_label(egui::Color32::RED, t!("ui.invalid_command", e));
+            }
+        }
+    }
+
+    fn process_control(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            // 通过块作用域限制锁的生命周期
+            let processing = {
+                let lock = self.processing.lock().unwrap();
+                *lock
+            };
+            // 开始处理按钮
+            if ui
+                .add_enabled(!processing, egui::Button::new(t!("ui.start")))
+                .clicked()
+            {
+                self.start_processing();
+            }
+
+            // 停止: 终止当前任务并取消剩余任务
+            if ui
+                .add_enabled(processing, egui::Button::new(t!("ui.stop")))
+                .clicked()
+            {
+                *self.state.cancel_requested.lock().unwrap() = true;
+            }
+
+            if ui
+                .checkbox(&mut self.continue_on_error, t!("ui.continue_on_error"))
+                .changed()
+            {
+                self.save_config();
+            }
+
+            ui.label(t!("ui.on_conflict"));
+            let old_policy = self.collision_policy;
+            egui::ComboBox::from_id_source("collision_policy")
+                .selected_text(self.collision_policy.label())
+                .show_ui(ui, |ui| {
+                    for policy in CollisionPolicy::ALL {
+                        ui.selectable_value(&mut self.collision_policy, policy, policy.label());
+                    }
+                });
+            if self.collision_policy != old_policy {
+                self.save_config();
+            }
+
+            let log_label = if self.show_log_panel {
+                t!("log.hide")
+            } else {
+                t!("log.show")
+            };
+            if ui.button(log_label).clicked() {
+                self.show_log_panel = !self.show_log_panel;
+            }
+        });
+    }
+
+    // 检查参数和输出冲突后开始处理
+    fn start_processing(&mut self) {
+        if *self.processing.lock().unwrap() || !self.pending_conflicts.is_empty() {
+            return;
+        }
+        if let Err(e) = self.validate_tasks() {
+            *self.state.message.lock().unwrap() = t!("common.error", e);
+            return;
+        }
+        self.prepare_batch_tasks();
+        let conflicts = collision::find_conflicts(&self.batch_queue);
+        if !conflicts.is_empty() && self.collision_policy == CollisionPolicy::Ask {
+            // 由冲突窗口决定处理方式后再开始
+            self.pending_conflicts = conflicts;
+            return;
+        }
+        self.start_batch(self.collision_policy);
+    }
+
+    // 按冲突策略确定每个任务的输出后启动处理线程
+    fn start_batch(&mut self, policy: CollisionPolicy) {
+        collision::resolve(&mut self.batch_queue, policy);
+        self.pending_conflicts.clear();
+
+        let state = self.state.clone();
+        *state.cancel_requested.lock().unwrap() = false;
+        let tasks = self.batch_queue.clone();
+        let processing_flag = self.processing.clone();
+        let options = batch::BatchOptions {
+            continue_on_error: self.continue_on_error,
+            verify: self.verify.clone(),
+        };
+
+        // 启动处理线程
+        *processing_flag.lock().unwrap() = true;
+        std::thread::spawn(move || {
+            batch::run_batch(tasks, &state, &options);
+            *processing_flag.lock().unwrap() = false; // 关键修改点
+        });
+    }
+
+    // 开始处理前列出输出冲突, 由用户选择处理方式
+    fn conflict_dialog(&mut self, ctx: &egui::Context) {
+        if self.pending_conflicts.is_empty() {
+            return;
+        }
+        let mut choice: Option<Option<CollisionPolicy>> = None;
+        egui::Window::new(t!("conflict.title"))
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(t!("conflict.count", self.pending_conflicts.len()));
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        for conflict in &self.pending_conflicts {
+                            ui.label(t!(
+                                "ui.task_item",
+                                conflict.index + 1,
+                                conflict.description()
+                            ));
+                        }
+                    });
+                ui.label(t!("conflict.note"));
+                ui.horizontal(|ui| {
+                    for policy in [
+                        CollisionPolicy::Overwrite,
+                        CollisionPolicy::Skip,
+                        CollisionPolicy::AppendCounter,
+                    ] {
+                        if ui.button(policy.label()).clicked() {
+                            choice = Some(Some(policy));
+                        }
+                    }
+                    if ui.button(t!("common.cancel")).clicked() {
+                        choice = Some(None);
+                    }
+                });
+            });
+        match choice {
+            Some(Some(policy)) => self.start_batch(policy),
+            Some(None) => self.pending_conflicts.clear(),
+            None => {}
+        }
+    }
+
+    fn log_source_label(&self, source: LogSource) -> String {
+        match source {
+            LogSource::Live => t!("log.live").to_string(),
+            LogSource::App => t!("log.app").to_string(),
+            LogSource::Task(i) => match self.batch_queue.get(i) {
+                Some(task) => t!("ui.task_item", i + 1, task.input_path),
+                None => t!("ui.task", i + 1),
+            },
+        }
+    }
+
+    // 日志面板: 运行中任务的实时输出、已完成任务的日志文件和程序日志
+    fn log_panel(&mut self, ui: &mut egui::Ui) {
+        let mut source = self.log_source;
+        ui.horizontal(|ui| {
+            ui.label(t!("log.label"));
+            egui::ComboBox::from_id_source("log_source")
+                .selected_text(self.log_source_label(source))
+                .width(400.0)
+                .show_ui(ui, |ui| {
+                    for option in [LogSource::Live, LogSource::App] {
+                        ui.selectable_value(&mut source, option, self.log_source_label(option));
+                    }
+                    // 只列出已生成日志文件的任务
+                    for (i, task) in self.batch_queue.iter().enumerate() {
+                        if Path::new(&task.log_path).exists() {
+                            let option = LogSource::Task(i);
+                            ui.selectable_value(&mut source, option, self.log_source_label(option));
+                        }
+                    }
+                });
+            if ui.button(t!("common.refresh")).clicked() {
+                self.log_file_cache = None;
+            }
+        });
+        self.log_source = source;
+
+        let text = match source {
+            LogSource::Live => self.state.live_log.lock().unwrap().join("\n"),
+            LogSource::App => logging::app_log_lines().join("\n"),
+            LogSource::Task(i) => {
+                let path = self
+                    .batch_queue
+                    .get(i)
+                    .map(|t| t.log_path.clone())
+                    .unwrap_or_default();
+                // 日志文件只在切换或刷新时读取
+                match &self.log_file_cache {
+                    Some((cached, content)) if *cached == path => content.clone(),
+                    _ => {
+                        let content = logging::read_log_file(&path);
+                        self.log_file_cache = Some((path, content.clone()));
+                        content
+                    }
                 }
-            })
-            .collect();
+            }
+        };
+
+        egui::ScrollArea::both()
+            .id_source("log_text")
+            .stick_to_bottom(true)
+            .auto_shrink([false, false])
+            .show(ui, |ui| {
+                ui.add(
+                    egui::TextEdit::multiline(&mut text.as_str())
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+            });
     }
-}
 
-// 清理文件名中的多余点
-fn sanitize_filename(filename: &str) -> String {
-    // 正则表达式匹配非中文、字母、数字、下划线的字符
-    let re = Regex::new(r"[^A-Za-z0-9_\.\/\u{4e00}-\u{9fff}]+").unwrap();
-    let reg_filename = re.replace_all(filename, "").to_string();
-
-    // 分离文件名和扩展名
-    let (stem, extension) = reg_filename.rsplit_once('.').unwrap_or((&reg_filename, ""));
-
-    // 处理主文件名部分
-    let sanitized_stem = stem
-        .chars()
-        .filter(|&c| c != '.' || c == '.') // 保留第一个点（如果有）
-        .collect::<String>()
-        .replace(".", ""); // 去掉所有点
-
-    // 重新组合
-    if extension.is_empty() {
-        sanitized_stem
-    } else {
-        format!("{}.{}", sanitized_stem, extension)
+    fn progress_display(&self, ui: &mut egui::Ui) {
+        let progress = *self.state.progress.lock().unwrap();
+        ui.add(
+            egui::ProgressBar::new(progress)
+                .text(t!("ui.progress", format!("{:.1}", progress * 100.0))),
+        );
+
+        let msg = self.state.message.lock().unwrap().clone();
+        ui.label(msg);
+
+        // 失败原因和处理建议
+        if let Some(error) = self.state.last_error.lock().unwrap().as_ref() {
+            error_details_ui(ui, error);
+        }
+
+        self.batch_summary(ui);
     }
-}
 
-// 重命名文件（实际文件操作）
-fn rename_file(path: &Path) -> std::io::Result<PathBuf> {
-    let filename = path
-        .file_name()
-        .and_then(|n| n.to_str())
-        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid filename"))?;
+    // 输出校验设置
+    fn verify_settings_panel(&mut self, ui: &mut egui::Ui) {
+        let old = self.verify.clone();
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.verify.enabled, t!("verify.enabled"));
+            ui.add_enabled_ui(self.verify.enabled, |ui| {
+                ui.label(t!("verify.tolerance"));
+                ui.add(
+                    egui::DragValue::new(&mut self.verify.tolerance_secs)
+                        .speed(0.1)
+                        .clamp_range(0.0..=60.0),
+                );
+                ui.checkbox(&mut self.verify.full_decode, t!("verify.full_decode"));
+            });
+        });
+        if self.verify != old {
+            self.save_config();
+        }
+    }
+
+    // 监视文件夹: 新文件写入完成后按预设自动处理
+    fn watch_panel(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing(t!("watch.title"), |ui| {
+            let old = self.watch.clone();
+            let running = self.watch_handle.is_some();
+            ui.add_enabled_ui(!running, |ui| {
+                for (label, dir) in [
+                    (t!("watch.input_dir"), &mut self.watch.input_dir),
+                    (t!("watch.output_dir"), &mut self.watch.output_dir),
+                    (t!("watch.archive_dir"), &mut self.watch.archive_dir),
+                ] {
+                    ui.horizontal(|ui| {
+                        ui.label(label);
+                        ui.text_edit_singleline(dir);
+                        if ui.button(t!("common.browse")).clicked()
+                            && let Some(path) = rfd::FileDialog::new().pick_folder()
+                        {
+                            *dir = path.display().to_string();
+                        }
+                    });
+                }
+                ui.weak(t!("watch.dir_hint"));
+
+                ui.horizontal(|ui| {
+                    ui.label(t!("watch.preset"));
+                    let selected = if self.watch.preset.is_empty() {
+                        t!("watch.default_preset")
+                    } else {
+                        self.watch.preset.as_str()
+                    };
+                    egui::ComboBox::from_id_source("watch_preset")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.watch.preset,
+                                String::new(),
+                                t!("watch.default_preset"),
+                            );
+                            for preset in &self.presets {
+                                ui.selectable_value(
+                                    &mut self.watch.preset,
+                                    preset.name.clone(),
+                                    &preset.name,
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.label(t!("watch.stable_secs"));
+                    ui.add(egui::DragValue::new(&mut self.watch.stable_secs).clamp_range(1..=3600));
+                    ui.checkbox(
+                        &mut self.watch.include_existing,
+                        t!("watch.include_existing"),
+                    );
+                });
+            });
+            if self.watch != old {
+                self.save_config();
+            }
+
+            ui.horizontal(|ui| {
+                if running {
+                    if ui.button(t!("watch.stop")).clicked()
+                        && let Some(handle) = self.watch_handle.take()
+                    {
+                        handle.stop();
+                    }
+                } else if ui.button(t!("watch.start")).clicked() {
+                    self.start_watch();
+                }
+            });
+            if !self.watch_error.is_empty() {
+                ui.colored_label(egui::Color32::RED, &self.watch_error);
+            }
+
+            let Some(handle) = &self.watch_handle else {
+                return;
+            };
+            let status = handle.status.lock().unwrap().clone();
+            ui.label(&status.message);
+            let progress = *handle.state.progress.lock().unwrap();
+            if progress > 0.0 {
+                ui.add(egui::ProgressBar::new(progress).show_percentage());
+            }
+            egui::Grid::new("watch_history")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (input, status) in status.history.iter().rev().take(20) {
+                        ui.label(input);
+                        ui.colored_label(status_color(*status), status.label());
+                        ui.end_row();
+                    }
+                });
+        });
+    }
 
-    let new_name = sanitize_filename(filename);
-    let new_path = path.with_file_name(new_name);
+    fn start_watch(&mut self) {
+        let preset = if self.watch.preset.is_empty() {
+            // 与命令行一致: 默认编码设置, 输出到全局输出目录
+            Preset {
+                output_dir: self.output_dir.clone(),
+                output_template: DEFAULT_TEMPLATE.to_owned(),
+                ..Preset::default()
+            }
+        } else {
+            match preset::find_preset(&self.presets, &self.watch.preset) {
+                Some(p) => p.clone(),
+                None => {
+                    self.watch_error = t!("cli.preset_not_found", self.watch.preset);
+                    return;
+                }
+            }
+        };
+        let job = watch::WatchJob {
+            settings: self.watch.clone(),
+            preset,
+            sanitize: self.sanitize.clone(),
+            global_args: self.global_args.clone(),
+            log_dir: self.log_dir.clone(),
+            verify: self.verify.clone(),
+            collision_policy: self.collision_policy,
+        };
+        match watch::start(job) {
+            Ok(handle) => {
+                self.watch_error.clear();
+                self.watch_handle = Some(handle);
+            }
+            Err(e) => self.watch_error = e,
+        }
+    }
 
-    if path != new_path {
-        std::fs::rename(path, &new_path)?;
+    // 每个任务的处理结果
+    fn batch_summary(&self, ui: &mut egui::Ui) {
+        let results = self.state.results.lock().unwrap().clone();
+        if results.is_empty() {
+            return;
+        }
+        ui.collapsing(t!("ui.results"), |ui| {
+            if ui.button(t!("report.export")).clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .add_filter("CSV", &["csv"])
+                    .add_filter("JSON", &["json"])
+                    .set_file_name("report.csv")
+                    .save_file()
+            {
+                let message = match report::export_report(&path, &self.batch_queue, &results) {
+                    Ok(()) => t!("report.exported", path.display()),
+                    Err(e) => t!("common.error", e),
+                };
+                *self.state.message.lock().unwrap() = message;
+            }
+            egui::Grid::new("batch_summary")
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    for (i, result) in results.iter().enumerate() {
+                        let input = self
+                            .batch_queue
+                            .get(i)
+                            .map(|t| t.input_path.as_str())
+                            .unwrap_or("");
+                        ui.label(format!("{}. {}", i + 1, input));
+                        ui.colored_label(status_color(result.status), result.status.label());
+                        match &result.error {
+                            Some(error) => {
+                                ui.collapsing(error.to_string(), |ui| {
+                                    error_details_ui(ui, error);
+                                });
+                            }
+                            None => {
+                                ui.label(result.warnings.join("; "));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
     }
 
-    Ok(new_path)
+    fn prepare_batch_tasks(&mut self) {
+        self.batch_queue = (0..self.sources.len()).map(|i| self.plan_task(i)).collect();
+        self.file_rows.invalidate_statuses();
+    }
 }
 
+// 根据模板生成输出路径. 源文件保持原样, 只清理生成的文件名, 输出目录按原样使用
 fn generate_output_path(
-    input_path: &str,
+    input: &TemplateInput,
     output_dir: &str,
     template: &str,
-    rotation: i32,
-) -> (String, String) {
-    let now = Local::now();
-    let mut input_path = PathBuf::from(input_path);
-
-    match rename_file(&input_path) {
-        Ok(new_path) => {
-            println!("重命名成功: {:?}", new_path);
-            input_path = new_path;
-        }
-        Err(e) => eprintln!("错误: {}", e),
-    }
-
-    let replacements = [
-        (
-            "{input_name}",
-            input_path.file_stem().unwrap().to_str().unwrap(),
-        ),
-        ("{rotation}", &rotation.to_string()),
-        ("{timestamp}", &now.format("%Y%m%d%H%M%S").to_string()),
-        ("{date}", &now.format("%Y-%m-%d").to_string()),
-        ("{time}", &now.format("%H-%M-%S").to_string()),
-    ];
-
-    let mut filename = template.to_string();
-    for (key, value) in &replacements {
-        filename = filename.replace(key, value);
-    }
-
-    // 自动添加文件扩展名
-    if let Some(ext) = input_path.extension() {
-        if !filename.contains('.') {
-            filename.push('.');
-            filename.push_str(ext.to_str().unwrap());
-        }
-    }
-
-    let output_path = Path::new(output_dir).join(filename);
-    // 正则表达式匹配非中文、字母、数字、下划线的字符
-    let re = Regex::new(r"[^A-Za-z0-9_\.\/\u{4e00}-\u{9fff}]+").unwrap();
-    (
-        re.replace_all(&output_path.to_string_lossy().into_owned(), "")
-            .to_string(),
-        input_path.to_string_lossy().into_owned(),
-    )
+    rules: &SanitizeRules,
+) -> String {
+    let mut filename = template::render(template, input);
+    let input_ext = Path::new(input.input_path)
+        .extension()
+        .map(|e| e.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let target = &input.params.encoding.container;
+
+    // 最后一级的扩展名: 选择了容器时替换为容器扩展名, 否则缺少时沿用源文件的扩展名.
+    // 只有源扩展名或已知容器才算扩展名, 像 "my.clip" 这样带点的文件名不受影响
+    let name_start = filename.rfind('/').map_or(0, |i| i + 1);
+    let has_ext = filename[name_start..]
+        .rsplit_once('.')
+        .is_some_and(|(_, ext)| {
+            ext.eq_ignore_ascii_case(&input_ext)
+                || container::CONTAINERS.contains(&ext.to_lowercase().as_str())
+        });
+    if !target.is_empty() {
+        if has_ext && let Some(dot) = filename.rfind('.') {
+            filename.truncate(dot);
+        }
+        filename.push('.');
+        filename.push_str(target);
+    } else if !has_ext && !input_ext.is_empty() {
+        filename.push('.');
+        filename.push_str(&input_ext);
+    }
+
+    Path::new(output_dir)
+        .join(sanitize::sanitize_relative_path(&filename, rules))
+        .to_string_lossy()
+        .into_owned()
+}
+
+// 变速设置控件
+fn speed_settings_ui(ui: &mut egui::Ui, speed: &mut SpeedSettings) {
+    ui.horizontal(|ui| {
+        ui.label(t!("speed.factor"));
+        ui.add(
+            egui::DragValue::new(&mut speed.factor)
+                .speed(0.05)
+                .clamp_range(speed::MIN_FACTOR..=speed::MAX_FACTOR)
+                .suffix("x"),
+        );
+        for factor in [0.5, 1.0, 2.0, 4.0] {
+            if ui.small_button(format!("{}x", factor)).clicked() {
+                speed.factor = factor;
+            }
+        }
+        ui.checkbox(&mut speed.drop_audio, t!("speed.drop_audio"));
+        ui.checkbox(&mut speed.reverse, t!("speed.reverse"))
+            .on_hover_text(t!("speed.reverse_hint", speed::MAX_REVERSE_SECS));
+    });
+}
+
+// 编码参数设置控件
+fn encoding_settings_ui(ui: &mut egui::Ui, enc: &mut EncodingSettings) {
+    let reencode = enc.video_codec != "copy";
+    ui.horizontal(|ui| {
+        ui.label(t!("enc.video_codec"));
+        codec_combo(ui, "video_codec", &mut enc.video_codec, VIDEO_CODECS);
+        // 直接复制流时无法使用质量、分辨率和滤镜
+        ui.add_enabled_ui(reencode, |ui| {
+            let max_crf = preset::max_crf(&enc.video_codec);
+            optional_u32(ui, "CRF", &mut enc.crf, 0..=max_crf, 23);
+            optional_u32(ui, t!("enc.width"), &mut enc.width, 16..=7680, 1920);
+            optional_u32(ui, t!("enc.height"), &mut enc.height, 16..=4320, 1080);
+        });
+    });
+    ui.horizontal(|ui| {
+        ui.label(t!("enc.video_filters"));
+        ui.add_enabled(
+            reencode,
+            egui::TextEdit::singleline(&mut enc.video_filters).hint_text(t!("enc.filters_hint")),
+        );
+    });
+    ui.horizontal(|ui| {
+        ui.label(t!("enc.container"));
+        let label = if enc.container.is_empty() {
+            t!("enc.same_container")
+        } else {
+            enc.container.as_str()
+        };
+        egui::ComboBox::from_id_source("container")
+            .selected_text(label)
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut enc.container, String::new(), t!("enc.same_container"));
+                for container in container::CONTAINERS {
+                    ui.selectable_value(&mut enc.container, container.to_string(), *container);
+                }
+            });
+    });
+    ui.horizontal(|ui| {
+        ui.label(t!("enc.audio_codec"));
+        codec_combo(ui, "audio_codec", &mut enc.audio_codec, AUDIO_CODECS);
+        ui.add_enabled_ui(enc.audio_codec != "copy", |ui| {
+            optional_u32(
+                ui,
+                t!("enc.audio_bitrate"),
+                &mut enc.audio_bitrate,
+                8..=1024,
+                192,
+            );
+        });
+    });
+}
+
+fn codec_combo(ui: &mut egui::Ui, id: &str, value: &mut String, codecs: &[&str]) {
+    egui::ComboBox::from_id_source(id)
+        .selected_text(value.as_str())
+        .show_ui(ui, |ui| {
+            for codec in codecs {
+                ui.selectable_value(value, codec.to_string(), *codec);
+            }
+        });
 }
 
-fn compare_times(time1: &str, time2: &str) -> std::cmp::Ordering {
-    let time1 = NaiveTime::from_str(time1).unwrap();
-    let time2 = NaiveTime::from_str(time2).unwrap();
+// 可选数值: 勾选后才生效
+fn optional_u32(
+    ui: &mut egui::Ui,
+    label: &str,
+    value: &mut Option<u32>,
+    range: std::ops::RangeInclusive<u32>,
+    default: u32,
+) {
+    let mut enabled = value.is_some();
+    if ui.checkbox(&mut enabled, label).changed() {
+        *value = enabled.then_some(value.unwrap_or(default));
+    }
+    if let Some(v) = value {
+        ui.add(egui::DragValue::new(v).clamp_range(range));
+    }
+}
+
+fn status_color(status: TaskStatus) -> egui::Color32 {
+    match status {
+        TaskStatus::Pending => egui::Color32::GRAY,
+        TaskStatus::Running => egui::Color32::LIGHT_BLUE,
+        TaskStatus::Done => egui::Color32::GREEN,
+        TaskStatus::Suspicious => egui::Color32::from_rgb(255, 165, 0),
+        TaskStatus::Failed => egui::Color32::RED,
+        TaskStatus::Skipped | TaskStatus::Cancelled => egui::Color32::YELLOW,
+    }
+}
 
-    time1.cmp(&time2)
+// 显示失败原因、解释和建议
+fn error_details_ui(ui: &mut egui::Ui, error: &TaskError) {
+    ui.group(|ui| {
+        ui.spacing_mut().item_spacing.y = 4.0;
+        let title = error.to_string();
+        ui.colored_label(egui::Color32::RED, &title);
+        if error.explanation() != title {
+            ui.label(error.explanation());
+        }
+        ui.label(t!("common.suggestion", error.suggestion()));
+        if !error.detail().is_empty() {
+            ui.monospace(error.detail());
+        }
+    });
 }
 
-fn process_task(task: BatchTask, state: &ProcessingState) -> Result<(), String> {
+fn process_task(task: BatchTask, state: &ProcessingState) -> Result<(), TaskError> {
     // 创建输出目录
     let output_path = Path::new(&task.output_path);
     if let Some(parent) = output_path.parent() {
-        fs::create_dir_all(parent).map_err(|e| format!("创建目录失败: {}", e))?;
+        fs::create_dir_all(parent).map_err(|e| t!("process.mkdir_failed", e))?;
     }
 
     // 构建基础命令
     let mut cmd = Command::new("ffmpeg");
     cmd.stdin(Stdio::null())
-        .stdout(Stdio::piped())
+        .stdout(Stdio::null())
         .stderr(Stdio::piped());
 
-    // 添加输入文件
-    cmd.arg("-i").arg(&task.input_path);
+    // 添加输入、裁剪、编码和输出参数
+    let args = command::build_ffmpeg_args(&task)?;
+    cmd.args(&args);
 
-    // 添加时间裁剪参数
-    match compare_times(&task.start_time, &task.end_time) {
-        std::cmp::Ordering::Less => {
-            cmd.arg("-ss").arg(&task.start_time);
-            cmd.arg("-to").arg(&task.end_time);
+    let command_line = command::format_command(&args);
+    logging::log(t!("log.final_command", command_line));
 
-            // 添加输出参数
-            cmd.args(&["-c:v", "copy", "-c:a", "copy"])
-                .arg(&task.output_path);
+    // 打开任务日志文件, 失败时只记录而不中断处理
+    let mut log_file = None;
+    if !task.log_path.is_empty() {
+        let log_path = Path::new(&task.log_path);
+        if let Some(parent) = log_path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        match fs::File::create(log_path) {
+            Ok(mut file) => {
+                let _ = writeln!(file, "# {}", command_line);
+                log_file = Some(file);
+            }
+            Err(e) => logging::log(t!("log.create_failed", task.log_path, e)),
         }
-        _ => {}
-    }
-
-    // 添加旋转元数据
-    if task.rotation != 0 {
-        // let rotation_filter = ;
-        cmd.args(&["-metadata:s:v"]);
-        cmd.args(&[format!("rotate={}", task.rotation)]);
-        cmd.args(&["-codec", "copy"]).arg(&task.output_path);
     }
-
-    println!("最终FFmpeg命令: {:?}", cmd.get_args().collect::<Vec<_>>());
+    state.live_log.lock().unwrap().clear();
 
     // 启动子进程
-    let mut child = cmd.spawn().map_err(|e| format!("启动FFmpeg失败: {}", e))?;
+    let mut child = cmd.spawn().map_err(|e| t!("process.spawn_failed", e))?;
 
     // 获取stderr管道
     let stderr = child
         .stderr
         .take()
-        .ok_or("无法获取stderr管道".to_string())?;
+        .ok_or(t!("process.no_stderr").to_string())?;
+
+    // 进度按输出时长计算, 变速后的输出时长与源文件不同
+    let expected_duration =
+        verify::expected_duration(&task, probe_duration(&task.input_path)).filter(|d| *d > 0.0);
 
-    // 启动进度监控线程
+    // 启动进度监控线程, 同时保存完整输出
     let state_progress = state.progress.clone();
-    std::thread::spawn(move || {
-        let reader = std::io::BufReader::new(stderr);
-        for line in reader.lines() {
-            if let Ok(line) = line {
-                if let Some(progress) = parse_ffmpeg_progress(&line) {
-                    *state_progress.lock().unwrap() = progress;
-                }
+    let live_log = state.live_log.clone();
+    let reader_thread = std::thread::spawn(move || {
+        // 保留最后的输出用于识别失败原因
+        let mut tail = Vec::new();
+        read_output_lines(stderr, |segment| {
+            if let Some(file) = log_file.as_mut() {
+                let _ = writeln!(file, "{}", segment);
             }
-        }
+            logging::push_capped(&mut live_log.lock().unwrap(), segment.to_string());
+            if let Some(seconds) = parse_ffmpeg_time(segment)
+                && let Some(total) = expected_duration
+            {
+                *state_progress.lock().unwrap() = (seconds / total).clamp(0.0, 1.0) as f32;
+            }
+            tail.push(segment.to_string());
+            if tail.len() > 200 {
+                tail.remove(0);
+            }
+        });
+        tail
     });
 
-    // 等待处理完成
-    let status = child
-        .wait()
-        .map_err(|e| format!("等待FFmpeg进程失败: {}", e))?;
+    // 等待处理完成, 期间响应停止请求
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(|e| t!("process.wait_failed", e))? {
+            break status;
+        }
+        if *state.cancel_requested.lock().unwrap() {
+            let _ = child.kill();
+            let _ = child.wait();
+            let _ = reader_thread.join();
+            return Err(TaskError::Cancelled);
+        }
+        std::thread::sleep(std::time::Duration::from_millis(100));
+    };
+    let tail = reader_thread.join().unwrap_or_default();
 
     if status.success() {
         Ok(())
     } else {
-        Err(format!("FFmpeg处理失败，退出码: {:?}", status.code()))
+        let (kind, detail) = ffmpeg_error::classify(&tail);
+        Err(TaskError::Ffmpeg {
+            kind,
+            exit_code: status.code(),
+            detail,
+        })
+    }
+}
+
+// 逐行读取 ffmpeg 的输出. ffmpeg 用 \r 刷新进度行, 其余输出以 \n 结尾,
+// 读到任意一种行尾都立即处理, 不等待下一次进度刷新
+fn read_output_lines(mut reader: impl Read, mut handle: impl FnMut(&str)) {
+    let mut pending = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let n = match reader.read(&mut chunk) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        pending.extend_from_slice(&chunk[..n]);
+        while let Some(end) = pending.iter().position(|b| matches!(b, b'\r' | b'\n')) {
+            let line: Vec<u8> = pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line[..end]);
+            if !line.trim().is_empty() {
+                handle(&line);
+            }
+        }
+    }
+    // 最后一行可能没有行尾
+    let line = String::from_utf8_lossy(&pending);
+    if !line.trim().is_empty() {
+        handle(&line);
     }
 }
 
-fn parse_ffmpeg_progress(line: &str) -> Option<f32> {
-    // 示例解析逻辑，实际需要根据FFmpeg输出调整
+// 从 ffmpeg 进度行中读取已输出的时长(秒)
+fn parse_ffmpeg_time(line: &str) -> Option<f64> {
     if line.contains("time=") {
         let time_str = line.split("time=").nth(1)?.split(' ').next()?;
         let parts: Vec<&str> = time_str.split(':').collect();
         match parts.len() {
             3 => {
                 // HH:MM:SS.ms
-                let hours: f32 = parts[0].parse().ok()?;
-                let minutes: f32 = parts[1].parse().ok()?;
-                let seconds: f32 = parts[2].parse().ok()?;
-                Some((hours * 3600.0 + minutes * 60.0 + seconds) / 100.0)
+                let hours: f64 = parts[0].parse().ok()?;
+                let minutes: f64 = parts[1].parse().ok()?;
+                let seconds: f64 = parts[2].parse().ok()?;
+                Some(hours * 3600.0 + minutes * 60.0 + seconds)
             }
             2 => {
                 // MM:SS.ms
-                let minutes: f32 = parts[0].parse().ok()?;
-                let seconds: f32 = parts[1].parse().ok()?;
-                Some((minutes * 60.0 + seconds) / 100.0)
+                let minutes: f64 = parts[0].parse().ok()?;
+                let seconds: f64 = parts[1].parse().ok()?;
+                Some(minutes * 60.0 + seconds)
             }
             _ => None,
         }
@@ -837,62 +3038,16 @@ fn parse_ffmpeg_progress(line: &str) -> Option<f32> {
     }
 }
 
-fn setup_fonts(ctx: &egui::Context) {
-    // 或者使用嵌入的字体文件（需将字体文件放在项目目录中）
-    // let font_data = include_bytes!("../fonts/SourceHanSansSC-Regular.otf");
-
-    let mut fonts = FontDefinitions::default();
-
-    // 方式2：使用默认字体补充中文（推荐）
-    fonts.font_data.insert(
-        "my_font".to_owned(),
-        egui::FontData::from_static(include_bytes!(
-            //"/usr/share/fonts/truetype/wqy/wqy-zenhei.ttc"
-            "../fonts/wqy-microhei.ttc"
-        )),
-    );
-
-    // 设置主要字体
-    fonts
-        .families
-        .entry(FontFamily::Proportional)
-        .or_default()
-        .insert(0, "my_font".to_owned());
-
-    // 设置等宽字体
-    fonts
-        .families
-        .entry(FontFamily::Monospace)
-        .or_default()
-        .push("my_font".to_owned());
-
-    ctx.set_fonts(fonts);
-
-    // 调整默认字体大小
-    let mut style = (*ctx.style()).clone();
-    style.text_styles = [
-        (
-            egui::TextStyle::Heading,
-            FontId::new(20.0, FontFamily::Proportional),
-        ),
-        (
-            egui::TextStyle::Body,
-            FontId::new(14.0, FontFamily::Proportional),
-        ),
-        (
-            egui::TextStyle::Button,
-            FontId::new(14.0, FontFamily::Proportional),
-        ),
-        (
-            egui::TextStyle::Monospace,
-            FontId::new(14.0, FontFamily::Monospace),
-        ),
-    ]
-    .into();
-    ctx.set_style(style);
-}
-
 fn main() {
+    // 带参数启动时以命令行模式运行
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // 命令行输出和窗口标题都需要先确定界面语言
+    let language = read_config(&default_config_path()).and_then(|c| c.language);
+    i18n::set_language(language.unwrap_or_else(Language::detect));
+    if let Some(code) = cli::run(&args) {
+        std::process::exit(code);
+    }
+
     // Load window icon
     let icon = {
         let icon_bytes = include_bytes!("../icons8-ffmpeg-48.png");
@@ -912,11 +3067,13 @@ fn main() {
         ..Default::default()
     };
     let _ = eframe::run_native(
-        "视频处理工具",
+        t!("ui.app_title"),
         options,
         Box::new(|_cc| {
-            setup_fonts(&_cc.egui_ctx);
-            Box::new(VideoProcessor::default())
+            let mut app = VideoProcessor::default();
+            app.loaded_font = fonts::load_fonts(&_cc.egui_ctx, &app.font);
+            fonts::apply_font_size(&_cc.egui_ctx, app.font.size);
+            Box::new(app)
         }),
     );
 }
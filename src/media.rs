@@ -0,0 +1,115 @@
+use chrono::{DateTime, Local};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+// 源文件的流信息, 用于文件名模板和容器兼容性检查
+#[derive(Clone, Default)]
+pub struct MediaInfo {
+    pub width: Option<u64>,
+    pub height: Option<u64>,
+    pub video_codec: Option<String>, // 第一条视频流, 不含封面图
+    pub audio_codec: Option<String>, // 第一条音频流
+    pub duration: Option<f64>,
+    pub frame_rate: Option<f64>,        // 第一条视频流的平均帧率
+    pub start_timecode: Option<String>, // 嵌入的起始时间码, 如 01:00:00;00
+    pub creation_time: Option<DateTime<Local>>,
+}
+
+// 作为源文件接受的扩展名, 包括摄像机常用的 MTS/MXF
+pub const MEDIA_EXTENSIONS: &[&str] = &[
+    "mp4", "mov", "mkv", "avi", "webm", "m4v", "mts", "m2ts", "ts", "mxf", "mpg", "mpeg", "wmv",
+    "flv", "3gp", "m4a", "mp3", "wav", "flac",
+];
+
+pub fn has_media_extension(path: &Path) -> bool {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .is_some_and(|e| MEDIA_EXTENSIONS.contains(&e.as_str()))
+}
+
+// 界面每帧都会用到, 按路径缓存 ffprobe 结果
+static CACHE: Mutex<Option<HashMap<String, MediaInfo>>> = Mutex::new(None);
+
+pub fn info(path: &str) -> MediaInfo {
+    if let Some(info) = cached_info(path) {
+        return info;
+    }
+    // 调用 ffprobe 期间不持有锁, 后台读取时界面仍可查询缓存
+    let info = probe(path);
+    CACHE
+        .lock()
+        .unwrap()
+        .get_or_insert_with(HashMap::new)
+        .insert(path.to_string(), info.clone());
+    info
+}
+
+// 只查询缓存, 尚未读取时返回 None 而不调用 ffprobe
+pub fn cached_info(path: &str) -> Option<MediaInfo> {
+    CACHE.lock().unwrap().as_ref()?.get(path).cloned()
+}
+
+fn probe(path: &str) -> MediaInfo {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "stream=codec_type,codec_name,width,height,avg_frame_rate,r_frame_rate:stream_tags=timecode:stream_disposition=attached_pic:format=duration:format_tags=creation_time,timecode",
+            "-of",
+            "json",
+            path,
+        ])
+        .stdin(Stdio::null())
+        .output();
+    let Ok(output) = output else {
+        return MediaInfo::default();
+    };
+    let Ok(json) = serde_json::from_slice::<Value>(&output.stdout) else {
+        return MediaInfo::default();
+    };
+
+    let streams = json["streams"].as_array().cloned().unwrap_or_default();
+    let video = streams.iter().find(|s| {
+        s["codec_type"].as_str() == Some("video") && s["disposition"]["attached_pic"] != 1
+    });
+    let audio = streams
+        .iter()
+        .find(|s| s["codec_type"].as_str() == Some("audio"));
+    let codec = |stream: Option<&Value>| {
+        stream
+            .and_then(|s| s["codec_name"].as_str())
+            .map(str::to_string)
+    };
+
+    let format = &json["format"];
+    MediaInfo {
+        width: video.and_then(|s| s["width"].as_u64()),
+        height: video.and_then(|s| s["height"].as_u64()),
+        video_codec: codec(video),
+        audio_codec: codec(audio),
+        duration: format["duration"].as_str().and_then(|d| d.parse().ok()),
+        frame_rate: video.and_then(|s| {
+            parse_rate(&s["avg_frame_rate"]).or_else(|| parse_rate(&s["r_frame_rate"]))
+        }),
+        // 时间码可能在容器标签、视频流或 tmcd 数据流中
+        start_timecode: format["tags"]["timecode"]
+            .as_str()
+            .or_else(|| streams.iter().find_map(|s| s["tags"]["timecode"].as_str()))
+            .map(str::to_string),
+        creation_time: format["tags"]["creation_time"]
+            .as_str()
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Local)),
+    }
+}
+
+// ffprobe 的帧率写成分数, 如 30000/1001; 未知时为 0/0
+fn parse_rate(value: &Value) -> Option<f64> {
+    let (num, den) = value.as_str()?.split_once('/')?;
+    let (num, den): (f64, f64) = (num.parse().ok()?, den.parse().ok()?);
+    (num > 0.0 && den > 0.0).then(|| num / den)
+}
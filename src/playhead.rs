@@ -0,0 +1,48 @@
+// 预览播放头: 当前位置(秒)和播放状态
+#[derive(Default)]
+pub struct Playhead {
+    pub position: f64,
+    pub playing: bool,
+    last_tick: Option<f64>, // 上一帧界面的时间, 用于播放时推进位置
+}
+
+impl Playhead {
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn seek(&mut self, position: f64, duration: Option<f64>) {
+        let end = duration.unwrap_or(f64::MAX);
+        self.position = position.clamp(0.0, end.max(0.0));
+    }
+
+    // 逐帧或逐秒移动时暂停播放
+    pub fn step(&mut self, delta: f64, duration: Option<f64>) {
+        self.playing = false;
+        self.seek(self.position + delta, duration);
+    }
+
+    pub fn toggle(&mut self, duration: Option<f64>) {
+        // 在结尾处播放时从头开始
+        if !self.playing && duration.is_some_and(|d| self.position >= d) {
+            self.position = 0.0;
+        }
+        self.playing = !self.playing;
+        self.last_tick = None;
+    }
+
+    // 每帧界面调用, 按实际经过的时间推进, 到结尾时停止
+    pub fn tick(&mut self, now: f64, duration: Option<f64>) {
+        if !self.playing {
+            self.last_tick = None;
+            return;
+        }
+        if let Some(last) = self.last_tick {
+            self.seek(self.position + (now - last), duration);
+        }
+        self.last_tick = Some(now);
+        if duration.is_some_and(|d| self.position >= d) {
+            self.playing = false;
+        }
+    }
+}
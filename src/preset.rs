@@ -0,0 +1,152 @@
+use serde::{Deserialize, Serialize};
+
+use crate::TaskParams;
+use crate::i18n::t;
+use crate::speed::SpeedSettings;
+
+// 时间裁剪方式
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum TrimMode {
+    // -ss 放在 -i 之后, 逐帧解码定位, 精确但较慢
+    #[default]
+    Accurate,
+    // -ss 放在 -i 之前, 按关键帧快速定位
+    Fast,
+}
+
+impl TrimMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            TrimMode::Accurate => t!("trim.accurate"),
+            TrimMode::Fast => t!("trim.fast"),
+        }
+    }
+}
+
+// 编码设置, 编码器为 "copy" 时直接复制流
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncodingSettings {
+    pub video_codec: String,
+    pub crf: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub video_filters: String, // 额外的 -vf 滤镜链
+    pub audio_codec: String,
+    pub audio_bitrate: Option<u32>, // kbps
+    pub container: String,          // 输出容器扩展名, 为空时与源文件相同
+}
+
+impl Default for EncodingSettings {
+    fn default() -> Self {
+        Self {
+            video_codec: "copy".to_owned(),
+            crf: None,
+            width: None,
+            height: None,
+            video_filters: String::new(),
+            audio_codec: "copy".to_owned(),
+            audio_bitrate: None,
+            container: String::new(),
+        }
+    }
+}
+
+pub const VIDEO_CODECS: &[&str] = &[
+    "copy",
+    "libx264",
+    "libx265",
+    "libvpx-vp9",
+    "libaom-av1",
+    "libsvtav1",
+];
+pub const AUDIO_CODECS: &[&str] = &["copy", "aac", "libopus", "libmp3lame", "flac"];
+
+// CRF 取值上限: x264/x265 为 51, VP9 和 AV1 编码器为 63
+pub fn max_crf(video_codec: &str) -> u32 {
+    match video_codec {
+        "libvpx-vp9" | "libaom-av1" | "libsvtav1" => 63,
+        _ => 51,
+    }
+}
+
+// 命名预设: 保存除裁剪时间外的全部处理设置
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preset {
+    pub name: String,
+    pub trim_mode: TrimMode,
+    pub rotation: i32,
+    pub encoding: EncodingSettings,
+    pub speed: SpeedSettings,
+    pub output_template: String,
+    pub output_dir: String,
+}
+
+impl Default for Preset {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            trim_mode: TrimMode::default(),
+            rotation: 0,
+            encoding: EncodingSettings::default(),
+            speed: SpeedSettings::default(),
+            output_template: crate::DEFAULT_TEMPLATE.to_owned(),
+            output_dir: "output".to_owned(),
+        }
+    }
+}
+
+impl Preset {
+    pub fn capture(
+        name: &str,
+        params: &TaskParams,
+        output_template: &str,
+        output_dir: &str,
+    ) -> Self {
+        Self {
+            name: name.to_owned(),
+            trim_mode: params.trim_mode,
+            rotation: params.rotation,
+            encoding: params.encoding.clone(),
+            speed: params.speed.clone(),
+            output_template: output_template.to_owned(),
+            output_dir: output_dir.to_owned(),
+        }
+    }
+
+    // 将预设应用到文件参数上, 保留原有的裁剪时间
+    pub fn apply_to(&self, params: &mut TaskParams) {
+        params.trim_mode = self.trim_mode;
+        params.rotation = self.rotation;
+        params.encoding = self.encoding.clone();
+        params.speed = self.speed.clone();
+    }
+}
+
+pub fn find_preset<'a>(presets: &'a [Preset], name: &str) -> Option<&'a Preset> {
+    presets.iter().find(|p| p.name == name)
+}
+
+// 新建或重命名时检查名称是否可用
+pub fn validate_preset_name(presets: &[Preset], name: &str) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(t!("preset.name_empty").to_string());
+    }
+    if find_preset(presets, name).is_some() {
+        return Err(t!("preset.name_exists", name));
+    }
+    Ok(())
+}
+
+// 名称冲突时追加序号, 如 "Fast 1080p (2)"
+pub fn unique_preset_name(presets: &[Preset], name: &str) -> String {
+    if find_preset(presets, name).is_none() {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| find_preset(presets, candidate).is_none())
+        .unwrap()
+}
@@ -0,0 +1,63 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::collision::CollisionPolicy;
+use crate::command::ExtraArgs;
+use crate::i18n::t;
+use crate::sanitize::SanitizeRules;
+use crate::verify::VerifySettings;
+use crate::{BatchTask, SourceEntry, TaskParams};
+
+const PROJECT_VERSION: u32 = 1;
+
+// 项目文件: 文件列表、每个文件的参数以及输出设置
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct Project {
+    pub version: u32,
+    pub sources: Vec<SourceEntry>,
+    pub default_params: TaskParams,
+    pub output_dir: String,
+    pub output_template: String,
+    pub global_args: ExtraArgs,
+    pub batch_queue: Vec<BatchTask>,
+    // 以下输出设置在旧版项目文件中没有, 为 None 时沿用当前设置
+    pub sanitize: Option<SanitizeRules>,
+    pub collision_policy: Option<CollisionPolicy>,
+    pub log_dir: Option<String>,
+    pub verify: Option<VerifySettings>,
+}
+
+impl Default for Project {
+    fn default() -> Self {
+        Self {
+            version: PROJECT_VERSION,
+            sources: Vec::new(),
+            default_params: TaskParams::default(),
+            output_dir: "output".to_owned(),
+            output_template: crate::DEFAULT_TEMPLATE.to_owned(),
+            global_args: ExtraArgs::default(),
+            batch_queue: Vec::new(),
+            sanitize: None,
+            collision_policy: None,
+            log_dir: None,
+            verify: None,
+        }
+    }
+}
+
+pub fn save_project(path: &Path, project: &Project) -> Result<(), String> {
+    let json =
+        serde_json::to_string_pretty(project).map_err(|e| t!("project.serialize_failed", e))?;
+    fs::write(path, json).map_err(|e| t!("project.write_failed", e))
+}
+
+pub fn load_project(path: &Path) -> Result<Project, String> {
+    let json = fs::read_to_string(path).map_err(|e| t!("project.read_failed", e))?;
+    let project: Project = serde_json::from_str(&json).map_err(|e| t!("project.invalid", e))?;
+    if project.version > PROJECT_VERSION {
+        return Err(t!("project.version", project.version, PROJECT_VERSION));
+    }
+    Ok(project)
+}
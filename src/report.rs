@@ -0,0 +1,197 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+use crate::BatchTask;
+use crate::batch::TaskResult;
+use crate::i18n::t;
+
+// 报告中的一行, 对应批处理中的一个任务
+#[derive(Serialize)]
+pub struct ReportRow {
+    pub index: usize,
+    pub input_path: String,
+    pub output_path: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub trim_mode: String,
+    pub rotation: i32,
+    pub video_codec: String,
+    pub crf: Option<u32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub video_filters: String,
+    pub audio_codec: String,
+    pub audio_bitrate: Option<u32>,
+    pub extra_input_args: String,
+    pub extra_output_args: String,
+    pub input_size_bytes: Option<u64>,
+    pub output_size_bytes: Option<u64>,
+    pub input_duration_secs: Option<f64>,
+    pub output_duration_secs: Option<f64>,
+    pub elapsed_secs: f64,
+    pub status: String,
+    pub error: String,
+    pub warnings: String,
+}
+
+pub fn build_rows(tasks: &[BatchTask], results: &[TaskResult]) -> Vec<ReportRow> {
+    tasks
+        .iter()
+        .zip(results)
+        .enumerate()
+        .map(|(i, (task, result))| {
+            let params = &task.params;
+            let enc = &params.encoding;
+            ReportRow {
+                index: i + 1,
+                input_path: task.input_path.clone(),
+                output_path: task.output_path.clone(),
+                start_time: params.start_time.clone(),
+                end_time: params.end_time.clone(),
+                trim_mode: format!("{:?}", params.trim_mode),
+                rotation: params.rotation,
+                video_codec: enc.video_codec.clone(),
+                crf: enc.crf,
+                width: enc.width,
+                height: enc.height,
+                video_filters: enc.video_filters.clone(),
+                audio_codec: enc.audio_codec.clone(),
+                audio_bitrate: enc.audio_bitrate,
+                extra_input_args: join_args(&task.global_args.input, &params.extra_args.input),
+                extra_output_args: join_args(&task.global_args.output, &params.extra_args.output),
+                input_size_bytes: result.input_size,
+                output_size_bytes: result.output_size,
+                input_duration_secs: result.input_duration,
+                output_duration_secs: result.output_duration,
+                elapsed_secs: result.elapsed_secs,
+                status: format!("{:?}", result.status),
+                error: result
+                    .error
+                    .as_ref()
+                    .map(|e| e.to_string())
+                    .unwrap_or_default(),
+                warnings: result.warnings.join("; "),
+            }
+        })
+        .collect()
+}
+
+fn join_args(global: &str, task: &str) -> String {
+    [global.trim(), task.trim()]
+        .iter()
+        .filter(|s| !s.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn to_json(rows: &[ReportRow]) -> Result<String, String> {
+    serde_json::to_string_pretty(rows).map_err(|e| t!("report.json_failed", e))
+}
+
+const CSV_HEADERS: &[&str] = &[
+    "index",
+    "input_path",
+    "output_path",
+    "start_time",
+    "end_time",
+    "trim_mode",
+    "rotation",
+    "video_codec",
+    "crf",
+    "width",
+    "height",
+    "video_filters",
+    "audio_codec",
+    "audio_bitrate",
+    "extra_input_args",
+    "extra_output_args",
+    "input_size_bytes",
+    "output_size_bytes",
+    "input_duration_secs",
+    "output_duration_secs",
+    "elapsed_secs",
+    "status",
+    "error",
+    "warnings",
+];
+
+fn opt<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+impl ReportRow {
+    // 与 CSV_HEADERS 顺序一致
+    fn csv_values(&self) -> Vec<String> {
+        vec![
+            self.index.to_string(),
+            self.input_path.clone(),
+            self.output_path.clone(),
+            self.start_time.clone(),
+            self.end_time.clone(),
+            self.trim_mode.clone(),
+            self.rotation.to_string(),
+            self.video_codec.clone(),
+            opt(self.crf),
+            opt(self.width),
+            opt(self.height),
+            self.video_filters.clone(),
+            self.audio_codec.clone(),
+            opt(self.audio_bitrate),
+            self.extra_input_args.clone(),
+            self.extra_output_args.clone(),
+            opt(self.input_size_bytes),
+            opt(self.output_size_bytes),
+            opt(self.input_duration_secs.map(|d| format!("{:.3}", d))),
+            opt(self.output_duration_secs.map(|d| format!("{:.3}", d))),
+            format!("{:.3}", self.elapsed_secs),
+            self.status.clone(),
+            self.error.clone(),
+            self.warnings.clone(),
+        ]
+    }
+}
+
+pub fn to_csv(rows: &[ReportRow]) -> String {
+    let mut csv = CSV_HEADERS.join(",");
+    csv.push('\n');
+    for row in rows {
+        let line = row
+            .csv_values()
+            .iter()
+            .map(|v| csv_field(v))
+            .collect::<Vec<_>>()
+            .join(",");
+        csv.push_str(&line);
+        csv.push('\n');
+    }
+    csv
+}
+
+// 包含逗号、引号或换行的字段加引号
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// 按扩展名选择格式: .csv 为 CSV, 其他为 JSON
+pub fn export_report(
+    path: &Path,
+    tasks: &[BatchTask],
+    results: &[TaskResult],
+) -> Result<(), String> {
+    let rows = build_rows(tasks, results);
+    let is_csv = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"));
+    let content = if is_csv {
+        to_csv(&rows)
+    } else {
+        to_json(&rows)?
+    };
+    fs::write(path, content).map_err(|e| t!("report.write_failed", e))
+}
@@ -0,0 +1,181 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+use crate::i18n::t;
+
+// 任何系统上都不能出现在文件名中的字符
+const FORBIDDEN: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+// 允许保留的标点
+const SAFE_PUNCTUATION: &[char] = &[
+    '_', '-', '.', '(', ')', '[', ']', '+', '=', ',', '\'', '&', '@', '!', '~', '#',
+];
+
+// Windows 保留的设备名, 不区分大小写, 带扩展名也不可用
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+// 不允许的字符的处理方式
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum InvalidCharMode {
+    #[default]
+    Replace,
+    Remove,
+}
+
+impl InvalidCharMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            InvalidCharMode::Replace => t!("sanitize.replace"),
+            InvalidCharMode::Remove => t!("sanitize.remove"),
+        }
+    }
+}
+
+// 空白字符的处理方式
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Serialize, Deserialize)]
+pub enum WhitespaceMode {
+    Keep,
+    #[default]
+    Underscore,
+    Remove,
+}
+
+impl WhitespaceMode {
+    pub fn label(self) -> &'static str {
+        match self {
+            WhitespaceMode::Keep => t!("whitespace.keep"),
+            WhitespaceMode::Underscore => t!("whitespace.underscore"),
+            WhitespaceMode::Remove => t!("whitespace.remove"),
+        }
+    }
+}
+
+// 输出文件名的清理规则
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SanitizeRules {
+    pub keep_unicode: bool, // 保留各语言的文字; 关闭时只保留 ASCII 字母数字和中文
+    pub invalid_chars: InvalidCharMode,
+    pub replacement: String,
+    pub whitespace: WhitespaceMode,
+    pub max_length: usize,    // 主文件名的最大字符数, 0 为不限制
+    pub avoid_reserved: bool, // 避开 CON、NUL 等 Windows 保留名
+}
+
+impl Default for SanitizeRules {
+    fn default() -> Self {
+        Self {
+            keep_unicode: true,
+            invalid_chars: InvalidCharMode::Replace,
+            replacement: "_".to_owned(),
+            whitespace: WhitespaceMode::Underscore,
+            max_length: 120,
+            avoid_reserved: true,
+        }
+    }
+}
+
+impl SanitizeRules {
+    fn is_allowed(&self, c: char) -> bool {
+        if c.is_control() || FORBIDDEN.contains(&c) {
+            return false;
+        }
+        if SAFE_PUNCTUATION.contains(&c) {
+            return true;
+        }
+        if self.keep_unicode {
+            c.is_alphanumeric() || is_combining_mark(c)
+        } else {
+            c.is_ascii_alphanumeric() || ('\u{4e00}'..='\u{9fff}').contains(&c)
+        }
+    }
+
+    // 替换字符串本身也要符合规则
+    fn replacement(&self) -> String {
+        self.replacement
+            .chars()
+            .filter(|&c| self.is_allowed(c))
+            .collect()
+    }
+}
+
+// 组合附加符号, 如 macOS 文件名中分解形式(NFD)的重音: e + U+0301
+fn is_combining_mark(c: char) -> bool {
+    static MARKS: OnceLock<Regex> = OnceLock::new();
+    let marks = MARKS.get_or_init(|| Regex::new(r"^[\p{Mn}\p{Mc}]$").unwrap());
+    marks.is_match(c.encode_utf8(&mut [0; 4]))
+}
+
+// 清理单个文件名(不含目录), 扩展名原样保留
+pub fn sanitize_filename(filename: &str, rules: &SanitizeRules) -> String {
+    let replacement = match rules.invalid_chars {
+        InvalidCharMode::Replace => rules.replacement(),
+        InvalidCharMode::Remove => String::new(),
+    };
+
+    let mut cleaned = String::new();
+    // 连续的无效字符只替换一次
+    let mut in_invalid_run = false;
+    for c in filename.chars() {
+        if c.is_whitespace() {
+            in_invalid_run = false;
+            match rules.whitespace {
+                WhitespaceMode::Keep => cleaned.push(' '),
+                WhitespaceMode::Underscore => cleaned.push('_'),
+                WhitespaceMode::Remove => {}
+            }
+        } else if rules.is_allowed(c) {
+            in_invalid_run = false;
+            cleaned.push(c);
+        } else if !in_invalid_run {
+            in_invalid_run = true;
+            cleaned.push_str(&replacement);
+        }
+    }
+
+    // 首尾的点和空格在部分系统上会被忽略或隐藏文件
+    let cleaned = cleaned.trim_matches(|c: char| c == '.' || c == ' ');
+    let (stem, extension) = match cleaned.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() => (stem.trim_end(), ext),
+        _ => (cleaned, ""),
+    };
+
+    let mut stem: String = if rules.max_length > 0 {
+        stem.chars().take(rules.max_length).collect()
+    } else {
+        stem.to_string()
+    };
+    if stem.is_empty() {
+        stem = "output".to_string();
+    }
+    if rules.avoid_reserved {
+        let base = stem.split('.').next().unwrap_or("");
+        if RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(base)) {
+            stem.push('_');
+        }
+    }
+
+    if extension.is_empty() {
+        stem
+    } else {
+        format!("{}.{}", stem, extension)
+    }
+}
+
+// 清理模板生成的相对路径, 每一级分别清理, 丢弃空目录和 "." ".."
+pub fn sanitize_relative_path(path: &str, rules: &SanitizeRules) -> String {
+    let parts: Vec<String> = path
+        .split(['/', '\\'])
+        .filter(|part| !part.trim().is_empty() && *part != "." && *part != "..")
+        .map(|part| sanitize_filename(part, rules))
+        .collect();
+    if parts.is_empty() {
+        sanitize_filename("", rules)
+    } else {
+        parts.join("/")
+    }
+}
@@ -0,0 +1,64 @@
+use eframe::egui::{self, Key, Modifiers};
+
+// 快捷键对应的操作
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    MarkIn,
+    MarkOut,
+    StepFrames(i64),
+    StepSeconds(i64),
+    TogglePlay,
+    StartProcessing,
+    RemoveSelected,
+    ToggleHelp,
+    CloseHelp,
+}
+
+impl Action {
+    // 输入框有焦点时, 字母、方向键和空格交给输入框处理
+    fn works_while_typing(self) -> bool {
+        matches!(
+            self,
+            Action::StartProcessing | Action::ToggleHelp | Action::CloseHelp
+        )
+    }
+}
+
+const BINDINGS: &[(Modifiers, Key, Action)] = &[
+    (Modifiers::NONE, Key::I, Action::MarkIn),
+    (Modifiers::NONE, Key::O, Action::MarkOut),
+    (Modifiers::NONE, Key::ArrowLeft, Action::StepFrames(-1)),
+    (Modifiers::NONE, Key::ArrowRight, Action::StepFrames(1)),
+    (Modifiers::SHIFT, Key::ArrowLeft, Action::StepSeconds(-1)),
+    (Modifiers::SHIFT, Key::ArrowRight, Action::StepSeconds(1)),
+    (Modifiers::NONE, Key::Space, Action::TogglePlay),
+    (Modifiers::COMMAND, Key::Enter, Action::StartProcessing),
+    (Modifiers::NONE, Key::Delete, Action::RemoveSelected),
+    (Modifiers::NONE, Key::F1, Action::ToggleHelp),
+    (Modifiers::NONE, Key::Escape, Action::CloseHelp),
+];
+
+// 帮助窗口中显示的按键及说明的文本键
+pub const HELP: &[(&str, &str)] = &[
+    ("I", "shortcut.mark_in"),
+    ("O", "shortcut.mark_out"),
+    ("← / →", "shortcut.step_frame"),
+    ("Shift + ← / →", "shortcut.step_second"),
+    ("Space", "shortcut.play"),
+    ("Ctrl + Enter", "shortcut.start"),
+    ("Delete", "shortcut.remove"),
+    ("F1", "shortcut.help"),
+];
+
+// 读取并消耗本帧按下的快捷键
+pub fn pressed(ctx: &egui::Context) -> Vec<Action> {
+    let typing = ctx.wants_keyboard_input();
+    ctx.input_mut(|input| {
+        BINDINGS
+            .iter()
+            .filter(|(_, _, action)| !typing || action.works_while_typing())
+            .filter(|(modifiers, key, _)| input.consume_key(*modifiers, *key))
+            .map(|(_, _, action)| *action)
+            .collect()
+    })
+}
@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::t;
+use crate::timecode::format_seconds;
+
+pub const MIN_FACTOR: f64 = 0.1;
+pub const MAX_FACTOR: f64 = 100.0;
+
+// 倒放需要把整段画面缓存在内存中, 只适用于短片段
+pub const MAX_REVERSE_SECS: f64 = 60.0;
+
+// 变速和倒放
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeedSettings {
+    pub factor: f64, // 播放速度倍数, 2.0 为两倍速
+    pub drop_audio: bool,
+    pub reverse: bool,
+}
+
+impl Default for SpeedSettings {
+    fn default() -> Self {
+        Self {
+            factor: 1.0,
+            drop_audio: false,
+            reverse: false,
+        }
+    }
+}
+
+impl SpeedSettings {
+    fn changes_speed(&self) -> bool {
+        (self.factor - 1.0).abs() > 1e-6
+    }
+
+    // 是否需要用滤镜重新生成时间戳, 此时不能直接复制流
+    pub fn changes_timing(&self) -> bool {
+        self.changes_speed() || self.reverse
+    }
+
+    // 变速后的时长
+    pub fn output_duration(&self, duration: f64) -> f64 {
+        duration / self.factor
+    }
+
+    // 检查倍数和倒放片段的长度, clip_duration 为裁剪后的源时长
+    pub fn validate(&self, clip_duration: Option<f64>) -> Result<(), String> {
+        if !(MIN_FACTOR..=MAX_FACTOR).contains(&self.factor) {
+            return Err(t!(
+                "speed.factor_range",
+                self.factor,
+                MIN_FACTOR,
+                MAX_FACTOR
+            ));
+        }
+        if self.reverse
+            && let Some(duration) = clip_duration
+            && duration > MAX_REVERSE_SECS
+        {
+            return Err(t!(
+                "speed.reverse_too_long",
+                format_seconds(duration),
+                format_seconds(MAX_REVERSE_SECS)
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn video_filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        if self.reverse {
+            filters.push("reverse".to_string());
+        }
+        if self.changes_speed() {
+            filters.push(format!("setpts=PTS/{}", format_factor(self.factor)));
+        }
+        filters
+    }
+
+    // 音频用 atempo 变速以保持音调
+    pub fn audio_filters(&self) -> Vec<String> {
+        let mut filters = Vec::new();
+        if self.reverse {
+            filters.push("areverse".to_string());
+        }
+        if self.changes_speed() {
+            filters.extend(atempo_chain(self.factor));
+        }
+        filters
+    }
+}
+
+// 旧版 ffmpeg 的 atempo 只接受 0.5 到 2.0, 超出范围时拆成多级相乘
+pub fn atempo_chain(factor: f64) -> Vec<String> {
+    let mut remaining = factor;
+    let mut chain = Vec::new();
+    while remaining > 2.0 {
+        chain.push("atempo=2".to_string());
+        remaining /= 2.0;
+    }
+    while remaining < 0.5 {
+        chain.push("atempo=0.5".to_string());
+        remaining /= 0.5;
+    }
+    if (remaining - 1.0).abs() > 1e-6 {
+        chain.push(format!("atempo={}", format_factor(remaining)));
+    }
+    chain
+}
+
+// 去掉多余的小数位, 如 1.5000000001 显示为 1.5
+fn format_factor(value: f64) -> String {
+    let text = format!("{:.6}", value);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stages(chain: &[String]) -> Vec<f64> {
+        chain
+            .iter()
+            .map(|s| s.strip_prefix("atempo=").unwrap().parse().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn atempo_chain_splits_out_of_range_factors() {
+        assert_eq!(atempo_chain(0.25), ["atempo=0.5", "atempo=0.5"]);
+        assert_eq!(atempo_chain(3.0), ["atempo=2", "atempo=1.5"]);
+        let mut expected = vec!["atempo=2"; 6];
+        expected.push("atempo=1.5625");
+        assert_eq!(atempo_chain(100.0), expected);
+        assert!(atempo_chain(1.0).is_empty());
+    }
+
+    #[test]
+    fn atempo_chain_multiplies_back_to_the_factor() {
+        for factor in [MIN_FACTOR, 0.3, 0.75, 1.25, 2.0, 7.0, MAX_FACTOR] {
+            let stages = stages(&atempo_chain(factor));
+            assert!(stages.iter().all(|s| (0.5..=2.0).contains(s)), "{}", factor);
+            let product: f64 = stages.iter().product();
+            assert!((product - factor).abs() < 1e-6, "{}", factor);
+        }
+    }
+
+    #[test]
+    fn output_duration_follows_speed() {
+        let settings = SpeedSettings {
+            factor: 4.0,
+            ..SpeedSettings::default()
+        };
+        assert_eq!(settings.output_duration(10.0), 2.5);
+        assert_eq!(settings.video_filters(), ["setpts=PTS/4"]);
+        assert_eq!(settings.audio_filters(), ["atempo=2", "atempo=2"]);
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use std::fmt::Write;
+use std::path::Path;
+
+use crate::TaskParams;
+use crate::i18n::t;
+use crate::media::{self, MediaInfo};
+use crate::timecode;
+
+// 模板中可用的变量及说明的文本键, 用于界面提示
+pub const VARIABLES: &[(&str, &str)] = &[
+    ("input_name", "var.input_name"),
+    ("ext", "var.ext"),
+    ("parent_dir", "var.parent_dir"),
+    ("rotation", "var.rotation"),
+    ("start", "var.start"),
+    ("end", "var.end"),
+    ("duration", "var.duration"),
+    ("width", "var.width"),
+    ("height", "var.height"),
+    ("codec", "var.codec"),
+    ("index", "var.index"),
+    ("segment", "var.segment"),
+    ("timestamp", "var.timestamp"),
+    ("date", "var.date"),
+    ("time", "var.time"),
+    ("creation_date", "var.creation_date"),
+];
+
+// 可以带 strftime 格式的变量, 如 {date:%Y%m}
+const DATE_VARIABLES: &[&str] = &["timestamp", "date", "time", "creation_date"];
+
+// 需要读取媒体信息的变量
+const MEDIA_VARIABLES: &[&str] = &["duration", "width", "height", "codec", "creation_date"];
+
+// 生成文件名所需的任务信息
+pub struct TemplateInput<'a> {
+    pub input_path: &'a str,
+    pub params: &'a TaskParams,
+    pub index: usize,   // 从 1 开始
+    pub segment: usize, // 从 1 开始
+}
+
+// 模板中的一段: 原样文本或变量
+enum Part<'a> {
+    Text(&'a str),
+    Variable {
+        name: &'a str,
+        format: Option<&'a str>,
+    },
+}
+
+fn parse(template: &str) -> Result<Vec<Part<'_>>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err(t!("template.extra_brace", &rest[open..]));
+        }
+        if open > 0 {
+            parts.push(Part::Text(&rest[..open]));
+        }
+        let close = rest[open..]
+            .find('}')
+            .ok_or_else(|| t!("template.unclosed", &rest[open..]))?
+            + open;
+        let inner = &rest[open + 1..close];
+        let (name, format) = match inner.split_once(':') {
+            Some((name, format)) => (name, Some(format)),
+            None => (inner, None),
+        };
+        parts.push(Part::Variable { name, format });
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(Part::Text(rest));
+    }
+    Ok(parts)
+}
+
+// 检查模板中的变量名和日期格式
+pub fn validate(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err(t!("template.empty").to_string());
+    }
+    if template.starts_with('/') || template.split('/').any(|p| p == "..") {
+        return Err(t!("template.relative").to_string());
+    }
+    for part in parse(template)? {
+        let Part::Variable { name, format } = part else {
+            continue;
+        };
+        if !VARIABLES.iter().any(|(v, _)| *v == name) {
+            return Err(t!("template.unknown_var", format!("{{{}}}", name)));
+        }
+        if let Some(format) = format {
+            if !DATE_VARIABLES.contains(&name) {
+                return Err(t!("template.no_format", format!("{{{}}}", name)));
+            }
+            if format.is_empty() || StrftimeItems::new(format).any(|i| i == Item::Error) {
+                return Err(t!("template.bad_date_format", format));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 格式无效时返回 None, 避免 chrono 在格式化时 panic
+fn format_date(time: &DateTime<Local>, format: &str) -> Option<String> {
+    let items: Vec<Item> = StrftimeItems::new(format).collect();
+    if items.contains(&Item::Error) {
+        return None;
+    }
+    let mut text = String::new();
+    write!(text, "{}", time.format_with_items(items.into_iter())).ok()?;
+    Some(text)
+}
+
+// 文件名中不能有冒号, 时间写成 00-01-30
+fn time_label(seconds: f64) -> String {
+    crate::format_duration(seconds).replace(':', "-")
+}
+
+// 按模板生成相对路径. 未知变量原样保留, 由 validate 负责报错
+pub fn render(template: &str, input: &TemplateInput) -> String {
+    let Ok(parts) = parse(template) else {
+        return template.to_string();
+    };
+    let path = Path::new(input.input_path);
+    let now = Local::now();
+    let needs_media = parts
+        .iter()
+        .any(|p| matches!(p, Part::Variable { name, .. } if MEDIA_VARIABLES.contains(name)));
+    let media = if needs_media {
+        media::info(input.input_path)
+    } else {
+        MediaInfo::default()
+    };
+
+    let mut output = String::new();
+    for part in parts {
+        let (name, format) = match part {
+            Part::Text(text) => {
+                output.push_str(text);
+                continue;
+            }
+            Part::Variable { name, format } => (name, format),
+        };
+        let date = |time: &DateTime<Local>, default: &str| {
+            format_date(time, format.unwrap_or(default)).unwrap_or_default()
+        };
+        let value = match name {
+            "input_name" => path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "ext" => path
+                .extension()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "parent_dir" => path
+                .parent()
+                .and_then(|p| p.file_name())
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "rotation" => input.params.rotation.to_string(),
+            "start" => input.params.start_time.replace(':', "-"),
+            "end" => input.params.end_time.replace(':', "-"),
+            "duration" => {
+                timecode::trim_range(&input.params.start_time, &input.params.end_time, &media)
+                    .unwrap_or_default()
+                    .output_duration(media.duration)
+                    .map(|d| time_label(input.params.speed.output_duration(d)))
+                    .unwrap_or_default()
+            }
+            "width" => media.width.map(|w| w.to_string()).unwrap_or_default(),
+            "height" => media.height.map(|h| h.to_string()).unwrap_or_default(),
+            "codec" => media.video_codec.clone().unwrap_or_default(),
+            "index" => input.index.to_string(),
+            "segment" => input.segment.to_string(),
+            "timestamp" => date(&now, "%Y%m%d%H%M%S"),
+            "date" => date(&now, "%Y-%m-%d"),
+            "time" => date(&now, "%H-%M-%S"),
+            // 没有创建时间时使用文件的修改时间
+            "creation_date" => media
+                .creation_time
+                .or_else(|| {
+                    std::fs::metadata(path)
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .map(DateTime::<Local>::from)
+                })
+                .map(|t| date(&t, "%Y-%m-%d"))
+                .unwrap_or_default(),
+            _ => {
+                match format {
+                    Some(format) => write!(output, "{{{}:{}}}", name, format),
+                    None => write!(output, "{{{}}}", name),
+                }
+                .ok();
+                continue;
+            }
+        };
+        output.push_str(&value);
+    }
+    output
+}
@@ -0,0 +1,519 @@
+use std::fmt;
+
+use crate::i18n::t;
+use crate::media::{self, MediaInfo};
+
+// 用户输入的时间点
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TimeSpec {
+    Seconds(f64),
+    // 帧号, 如 120f, 需要视频帧率换算
+    Frames(u64),
+    // SMPTE 时间码 HH:MM:SS:FF, 丢帧时间码的帧前用分号
+    Timecode(Timecode),
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Timecode {
+    pub hours: u64,
+    pub minutes: u64,
+    pub seconds: u64,
+    pub frames: u64,
+    pub drop_frame: bool,
+}
+
+impl TimeSpec {
+    // 支持 HH:MM:SS[.mmm]、MM:SS[.mmm]、秒数、帧号和时间码, 空字符串返回 None
+    pub fn parse(text: &str) -> Result<Option<TimeSpec>, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(None);
+        }
+        let invalid = || t!("time.invalid", text);
+
+        if let Some(frames) = text.strip_suffix(['f', 'F']) {
+            return parse_digits(frames)
+                .map(|f| Some(TimeSpec::Frames(f)))
+                .ok_or_else(invalid);
+        }
+
+        if text.contains(';') || text.split(':').count() == 4 {
+            return Timecode::parse(text).map(|tc| Some(TimeSpec::Timecode(tc)));
+        }
+
+        let fields: Vec<&str> = text.split(':').collect();
+        if fields.len() > 3 {
+            return Err(invalid());
+        }
+        // 最后一段为秒, 可带小数; 前面的分钟和秒必须是两位数
+        let (leading, last) = fields.split_at(fields.len() - 1);
+        let seconds = parse_seconds(last[0]).ok_or_else(invalid)?;
+        if leading.is_empty() {
+            return Ok(Some(TimeSpec::Seconds(seconds)));
+        }
+        if last[0].split('.').next().is_none_or(|s| s.len() != 2) || seconds >= 60.0 {
+            return Err(t!("time.field_range", text));
+        }
+        let mut total = seconds;
+        for (i, field) in leading.iter().enumerate() {
+            let value = parse_digits(field).ok_or_else(invalid)?;
+            let is_first = i == 0;
+            if !is_first && (field.len() != 2 || value >= 60) {
+                return Err(t!("time.field_range", text));
+            }
+            total += value as f64 * 60f64.powi((leading.len() - i) as i32);
+        }
+        Ok(Some(TimeSpec::Seconds(total)))
+    }
+
+    // 换算为从文件开头算起的秒数. 帧号按视频帧率换算, 时间码减去文件的起始时间码
+    pub fn to_seconds(self, info: &MediaInfo) -> Result<f64, String> {
+        let fps = || {
+            info.frame_rate
+                .filter(|fps| *fps > 0.0)
+                .ok_or_else(|| t!("time.no_frame_rate").to_string())
+        };
+        match self {
+            TimeSpec::Seconds(seconds) => Ok(seconds),
+            TimeSpec::Frames(frames) => Ok(frames as f64 / fps()?),
+            TimeSpec::Timecode(tc) => {
+                let fps = fps()?;
+                let frames = tc.to_frames(fps)?;
+                let start = start_timecode(info)
+                    .map(|start| start.to_frames(fps))
+                    .transpose()?
+                    .unwrap_or(0);
+                if frames < start {
+                    return Err(t!(
+                        "time.before_start_timecode",
+                        tc,
+                        info.start_timecode.as_deref().unwrap_or_default()
+                    ));
+                }
+                Ok((frames - start) as f64 / fps)
+            }
+        }
+    }
+}
+
+// 时间码每秒的帧数, 29.97 按 30 计
+fn nominal_rate(fps: f64) -> u64 {
+    fps.round().max(1.0) as u64
+}
+
+// 丢帧时间码每分钟跳过的帧号数: 29.97 为 2, 59.94 为 4; 其他帧率不支持丢帧
+fn dropped_per_minute(fps: f64) -> Option<u64> {
+    let nominal = nominal_rate(fps);
+    let is_ntsc = (fps - nominal as f64 * 1000.0 / 1001.0).abs() < 0.01;
+    (is_ntsc && (nominal == 30 || nominal == 60)).then_some(nominal / 15)
+}
+
+impl Timecode {
+    // HH:MM:SS:FF 或 HH:MM:SS;FF (也接受全部用分号分隔)
+    pub fn parse(text: &str) -> Result<Timecode, String> {
+        let invalid = || t!("time.invalid", text);
+        let fields: Vec<&str> = text.split([':', ';']).collect();
+        let [hours, minutes, seconds, frames] = fields[..] else {
+            return Err(invalid());
+        };
+        let field = |value: &str, max: u64| -> Result<u64, String> {
+            let n = parse_digits(value).ok_or_else(invalid)?;
+            if value.len() != 2 || n >= max {
+                return Err(t!("time.field_range", text));
+            }
+            Ok(n)
+        };
+        Ok(Timecode {
+            hours: parse_digits(hours).ok_or_else(invalid)?,
+            minutes: field(minutes, 60)?,
+            seconds: field(seconds, 60)?,
+            frames: parse_digits(frames).ok_or_else(invalid)?,
+            drop_frame: text.contains(';'),
+        })
+    }
+
+    // 换算为帧序号
+    pub fn to_frames(self, fps: f64) -> Result<u64, String> {
+        let nominal = nominal_rate(fps);
+        if self.frames >= nominal {
+            return Err(t!("time.frame_range", self, nominal));
+        }
+        // 小时数没有上限, 换算溢出时按字段超出范围处理
+        let overflow = || t!("time.field_range", self);
+        let total_minutes = self
+            .hours
+            .checked_mul(60)
+            .and_then(|m| m.checked_add(self.minutes))
+            .ok_or_else(overflow)?;
+        let frames = total_minutes
+            .checked_mul(60)
+            .and_then(|s| s.checked_add(self.seconds))
+            .and_then(|s| s.checked_mul(nominal))
+            .and_then(|f| f.checked_add(self.frames))
+            .ok_or_else(overflow)?;
+        if !self.drop_frame {
+            return Ok(frames);
+        }
+        let Some(dropped) = dropped_per_minute(fps) else {
+            return Err(t!("time.drop_frame_unsupported", format!("{:.3}", fps)));
+        };
+        // 除整十分钟外, 每分钟开头的几个帧号不存在
+        if self.seconds == 0 && self.frames < dropped && !self.minutes.is_multiple_of(10) {
+            return Err(t!("time.dropped_frame", self));
+        }
+        Ok(frames - dropped * (total_minutes - total_minutes / 10))
+    }
+
+    // 帧序号转换为时间码
+    pub fn from_frames(frames: u64, fps: f64, drop_frame: bool) -> Timecode {
+        let nominal = nominal_rate(fps);
+        let dropped = dropped_per_minute(fps).filter(|_| drop_frame);
+        let mut frames = frames;
+        if let Some(dropped) = dropped {
+            // 每十分钟实际有 10 * 60 * nominal - 9 * dropped 帧
+            let per_ten_minutes = 600 * nominal - 9 * dropped;
+            let per_minute = 60 * nominal - dropped;
+            let tens = frames / per_ten_minutes;
+            let rest = frames % per_ten_minutes;
+            frames += 9 * dropped * tens;
+            if rest > dropped {
+                frames += dropped * ((rest - dropped) / per_minute);
+            }
+        }
+        Timecode {
+            hours: frames / (3600 * nominal),
+            minutes: frames / (60 * nominal) % 60,
+            seconds: frames / nominal % 60,
+            frames: frames % nominal,
+            drop_frame: dropped.is_some(),
+        }
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let separator = if self.drop_frame { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, separator, self.frames
+        )
+    }
+}
+
+// 文件中嵌入的起始时间码, 无法解析时视为 00:00:00:00
+fn start_timecode(info: &MediaInfo) -> Option<Timecode> {
+    info.start_timecode
+        .as_deref()
+        .and_then(|tc| Timecode::parse(tc).ok())
+}
+
+// 把文件中的位置显示为时间码, 与剪辑软件一致地加上起始时间码.
+// 有起始时间码时沿用它的丢帧方式, 否则 29.97/59.94 默认使用丢帧时间码
+pub fn format_timecode(seconds: f64, info: &MediaInfo) -> Option<Timecode> {
+    let fps = info.frame_rate.filter(|fps| *fps > 0.0)?;
+    let start = start_timecode(info);
+    let drop_frame = start.map_or(dropped_per_minute(fps).is_some(), |tc| tc.drop_frame);
+    let offset = start.and_then(|tc| tc.to_frames(fps).ok()).unwrap_or(0);
+    let frames = (seconds.max(0.0) * fps).round() as u64 + offset;
+    Some(Timecode::from_frames(frames, fps, drop_frame))
+}
+
+fn parse_digits(text: &str) -> Option<u64> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+fn parse_seconds(text: &str) -> Option<f64> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, "0"));
+    parse_digits(whole)?;
+    parse_digits(fraction)?;
+    text.parse().ok()
+}
+
+// 裁剪范围, None 表示从头开始或处理到结尾
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub struct Trim {
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+}
+
+impl Trim {
+    // 输出的预期时长, 结束时间超出文件长度时以文件结尾为准
+    pub fn output_duration(&self, input_duration: Option<f64>) -> Option<f64> {
+        let end = match (self.end, input_duration) {
+            (Some(end), Some(duration)) => end.min(duration),
+            (Some(end), None) => end,
+            (None, duration) => duration?,
+        };
+        Some((end - self.start.unwrap_or(0.0)).max(0.0))
+    }
+}
+
+// 解析并检查开始和结束时间. 时间为 0 表示不裁剪该端, 与旧版本的默认值 0:00:00 兼容
+pub fn trim_range(start: &str, end: &str, info: &MediaInfo) -> Result<Trim, String> {
+    let resolve = |text: &str, label: &str| -> Result<Option<f64>, String> {
+        let Some(spec) = TimeSpec::parse(text).map_err(|e| format!("{}: {}", label, e))? else {
+            return Ok(None);
+        };
+        let seconds = spec
+            .to_seconds(info)
+            .map_err(|e| format!("{}: {}", label, e))?;
+        if let Some(duration) = info.duration
+            && seconds > duration
+        {
+            return Err(t!(
+                "time.beyond_duration",
+                label,
+                format_seconds(seconds),
+                format_seconds(duration)
+            ));
+        }
+        Ok((seconds > 0.0).then_some(seconds))
+    };
+    let start = resolve(start, t!("time.start"))?;
+    let end = resolve(end, t!("time.end"))?;
+    if let (Some(start), Some(end)) = (start, end)
+        && end <= start
+    {
+        return Err(t!("time.end_before_start").to_string());
+    }
+    if let (Some(start), Some(duration)) = (start, info.duration)
+        && start >= duration
+    {
+        return Err(t!(
+            "time.beyond_duration",
+            t!("time.start"),
+            format_seconds(start),
+            format_seconds(duration)
+        ));
+    }
+    Ok(Trim { start, end })
+}
+
+// 未选择文件时只能检查格式, 帧号和时长要等到有文件后再检查
+pub fn check_syntax(start: &str, end: &str) -> Result<(), String> {
+    for (text, label) in [(start, t!("time.start")), (end, t!("time.end"))] {
+        TimeSpec::parse(text).map_err(|e| format!("{}: {}", label, e))?;
+    }
+    Ok(())
+}
+
+// 读取源文件信息后检查裁剪范围
+pub fn task_trim(start: &str, end: &str, input_path: &str) -> Result<Trim, String> {
+    trim_range(start, end, &media::info(input_path))
+}
+
+// 传给 ffmpeg 的时间, 如 01:02:03.500
+pub fn format_seconds(seconds: f64) -> String {
+    let millis = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn info(duration: f64, frame_rate: Option<f64>) -> MediaInfo {
+        MediaInfo {
+            duration: Some(duration),
+            frame_rate,
+            ..MediaInfo::default()
+        }
+    }
+
+    #[test]
+    fn trim_range_accepts_open_ends() {
+        let info = info(60.0, Some(25.0));
+        assert_eq!(trim_range("", "", &info), Ok(Trim::default()));
+        assert_eq!(
+            trim_range("0:00:00", "00:30", &info),
+            Ok(Trim {
+                start: None,
+                end: Some(30.0)
+            })
+        );
+        assert_eq!(
+            trim_range("250f", "", &info),
+            Ok(Trim {
+                start: Some(10.0),
+                end: None
+            })
+        );
+    }
+
+    #[test]
+    fn trim_range_rejects_end_before_start() {
+        let info = info(60.0, None);
+        assert_eq!(
+            trim_range("20", "10", &info),
+            Err(t!("time.end_before_start").to_string())
+        );
+        assert_eq!(
+            trim_range("10", "10", &info),
+            Err(t!("time.end_before_start").to_string())
+        );
+    }
+
+    #[test]
+    fn trim_range_rejects_times_beyond_duration() {
+        let info = info(60.0, None);
+        assert_eq!(
+            trim_range("", "90", &info),
+            Err(t!(
+                "time.beyond_duration",
+                t!("time.end"),
+                "00:01:30.000",
+                "00:01:00.000"
+            ))
+        );
+        // 开始时间等于时长时没有可输出的内容
+        assert!(trim_range("60", "", &info).is_err());
+    }
+
+    #[test]
+    fn trim_range_reports_which_field_is_invalid() {
+        let info = info(60.0, None);
+        let error = trim_range("1:2:3:4:5", "", &info).unwrap_err();
+        assert!(error.starts_with(t!("time.start")));
+        let error = trim_range("", "abc", &info).unwrap_err();
+        assert!(error.starts_with(t!("time.end")));
+        // 没有帧率时无法换算帧号
+        assert_eq!(
+            trim_range("", "100f", &info),
+            Err(format!("{}: {}", t!("time.end"), t!("time.no_frame_rate")))
+        );
+    }
+
+    const NTSC: f64 = 30000.0 / 1001.0;
+
+    fn timecode(text: &str) -> Timecode {
+        Timecode::parse(text).unwrap()
+    }
+
+    #[test]
+    fn drop_frame_skips_frame_numbers_each_minute() {
+        let last = timecode("00:00:59;29").to_frames(NTSC).unwrap();
+        assert_eq!(last, 1799);
+        assert_eq!(
+            Timecode::from_frames(last + 1, NTSC, true).to_string(),
+            "00:01:00;02"
+        );
+        assert_eq!(timecode("00:01:00;02").to_frames(NTSC), Ok(1800));
+        assert_eq!(
+            timecode("00:01:00;00").to_frames(NTSC),
+            Err(t!("time.dropped_frame", "00:01:00;00"))
+        );
+        assert!(timecode("00:01:00;01").to_frames(NTSC).is_err());
+    }
+
+    #[test]
+    fn drop_frame_keeps_every_tenth_minute() {
+        let frames = timecode("00:10:00;00").to_frames(NTSC).unwrap();
+        assert_eq!(frames, 17982);
+        assert_eq!(
+            Timecode::from_frames(frames - 1, NTSC, true).to_string(),
+            "00:09:59;29"
+        );
+        assert_eq!(
+            Timecode::from_frames(frames, NTSC, true).to_string(),
+            "00:10:00;00"
+        );
+        assert_eq!(timecode("01:00:00;00").to_frames(NTSC), Ok(107892));
+    }
+
+    #[test]
+    fn drop_frame_round_trips() {
+        for fps in [NTSC, 60000.0 / 1001.0] {
+            for frames in (0..40000).step_by(7) {
+                let tc = Timecode::from_frames(frames, fps, true);
+                assert_eq!(tc.to_frames(fps), Ok(frames), "{}", tc);
+            }
+        }
+    }
+
+    #[test]
+    fn drop_frame_needs_ntsc_rate() {
+        assert_eq!(
+            timecode("00:00:01;00").to_frames(25.0),
+            Err(t!("time.drop_frame_unsupported", "25.000"))
+        );
+        assert_eq!(timecode("00:00:01:00").to_frames(25.0), Ok(25));
+        assert!(timecode("00:00:01:25").to_frames(25.0).is_err());
+    }
+
+    #[test]
+    fn huge_hours_do_not_overflow() {
+        for (text, fps) in [
+            ("99999999999999999:00:00:00", 25.0),
+            ("99999999999999999:00:00;00", NTSC),
+        ] {
+            let tc = timecode(text);
+            assert_eq!(
+                tc.to_frames(fps),
+                Err(t!("time.field_range", tc)),
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn time_spec_parses_supported_forms() {
+        assert_eq!(TimeSpec::parse("  "), Ok(None));
+        assert_eq!(TimeSpec::parse("90.5"), Ok(Some(TimeSpec::Seconds(90.5))));
+        assert_eq!(TimeSpec::parse("01:30"), Ok(Some(TimeSpec::Seconds(90.0))));
+        assert_eq!(
+            TimeSpec::parse("1:00:00.250"),
+            Ok(Some(TimeSpec::Seconds(3600.25)))
+        );
+        assert_eq!(TimeSpec::parse("120f"), Ok(Some(TimeSpec::Frames(120))));
+        assert_eq!(
+            TimeSpec::parse("01:00:00;00"),
+            Ok(Some(TimeSpec::Timecode(Timecode {
+                hours: 1,
+                minutes: 0,
+                seconds: 0,
+                frames: 0,
+                drop_frame: true
+            })))
+        );
+    }
+
+    #[test]
+    fn time_spec_rejects_malformed_input() {
+        for text in [
+            "abc",
+            "-5",
+            "1e3",
+            "1.",
+            "f",
+            "12xf",
+            "1:2:3:4:5",
+            "00:00:00:",
+        ] {
+            assert_eq!(
+                TimeSpec::parse(text),
+                Err(t!("time.invalid", text)),
+                "{}",
+                text
+            );
+        }
+        for text in ["1:60", "1:5", "0:61:00", "00:00:60:00", "00:60:00;00"] {
+            assert_eq!(
+                TimeSpec::parse(text),
+                Err(t!("time.field_range", text)),
+                "{}",
+                text
+            );
+        }
+    }
+}
@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::process::{Command, Stdio};
+
+use crate::BatchTask;
+use crate::command::{drops_audio, has_output_flag};
+use crate::container;
+use crate::i18n::t;
+use crate::timecode;
+
+// 处理完成后的输出校验设置
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VerifySettings {
+    pub enabled: bool,
+    pub tolerance_secs: f64, // 实际时长与预期时长允许的误差
+    pub full_decode: bool,   // 用 -f null 完整解码一遍
+}
+
+impl Default for VerifySettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            tolerance_secs: 1.0,
+            full_decode: false,
+        }
+    }
+}
+
+// 校验结论
+pub enum Verdict {
+    Passed,
+    Suspicious(Vec<String>), // 可以使用但与预期不符
+    Failed(String),          // 输出不可用
+}
+
+// 媒体文件的流数量和时长
+pub struct StreamSummary {
+    pub video: usize,
+    pub audio: usize,
+    pub duration: Option<f64>,
+}
+
+pub fn probe_streams(path: &str) -> Option<StreamSummary> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "stream=codec_type:format=duration",
+            "-of",
+            "json",
+            path,
+        ])
+        .stdin(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let json: Value = serde_json::from_slice(&output.stdout).ok()?;
+    let streams = json["streams"].as_array().cloned().unwrap_or_default();
+    let count = |kind: &str| {
+        streams
+            .iter()
+            .filter(|s| s["codec_type"].as_str() == Some(kind))
+            .count()
+    };
+    Some(StreamSummary {
+        video: count("video"),
+        audio: count("audio"),
+        duration: json["format"]["duration"]
+            .as_str()
+            .and_then(|d| d.parse().ok()),
+    })
+}
+
+// 根据裁剪范围、变速倍数和输入时长计算预期的输出时长
+pub fn expected_duration(task: &BatchTask, input_duration: Option<f64>) -> Option<f64> {
+    let params = &task.params;
+    let duration = timecode::task_trim(&params.start_time, &params.end_time, &task.input_path)
+        .ok()?
+        .output_duration(input_duration)?;
+    Some(params.speed.output_duration(duration))
+}
+
+pub fn verify_output(task: &BatchTask, settings: &VerifySettings) -> Verdict {
+    let Some(output) = probe_streams(&task.output_path) else {
+        return Verdict::Failed(t!("verify.unreadable").to_string());
+    };
+    let input = probe_streams(&task.input_path);
+
+    // 输入中存在的流在输出中也应存在
+    if let Some(input) = &input {
+        let video_dropped = has_output_flag(task, "-vn")
+            || container::is_audio_only(&container::of_path(&task.output_path));
+        if input.video > 0 && output.video == 0 && !video_dropped {
+            return Verdict::Failed(t!("verify.no_video").to_string());
+        }
+        if input.audio > 0 && output.audio == 0 && !drops_audio(task) {
+            return Verdict::Failed(t!("verify.no_audio").to_string());
+        }
+    }
+
+    if settings.full_decode
+        && let Err(e) = decode_check(&task.output_path)
+    {
+        return Verdict::Failed(e);
+    }
+
+    let mut warnings = Vec::new();
+    let expected = expected_duration(task, input.as_ref().and_then(|i| i.duration));
+    match (expected, output.duration) {
+        (Some(expected), Some(actual)) if (expected - actual).abs() > settings.tolerance_secs => {
+            warnings.push(t!(
+                "verify.duration_mismatch",
+                format!("{:.2}", actual),
+                format!("{:.2}", expected),
+                format!("{:.2}", settings.tolerance_secs)
+            ));
+        }
+        (_, None) => warnings.push(t!("verify.no_duration").to_string()),
+        _ => {}
+    }
+
+    if warnings.is_empty() {
+        Verdict::Passed
+    } else {
+        Verdict::Suspicious(warnings)
+    }
+}
+
+// 完整解码输出文件, 有任何错误输出即视为失败
+fn decode_check(path: &str) -> Result<(), String> {
+    let output = Command::new("ffmpeg")
+        .args(["-v", "error", "-nostdin", "-i", path, "-f", "null", "-"])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| t!("verify.decode_start_failed", e))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() || !stderr.trim().is_empty() {
+        let first = stderr.lines().next().unwrap_or("").to_string();
+        return Err(t!("verify.decode_error", first));
+    }
+    Ok(())
+}
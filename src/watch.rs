@@ -0,0 +1,372 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::batch::{self, TaskStatus};
+use crate::collision::{self, CollisionPolicy, OutputAction};
+use crate::command::{self, ExtraArgs};
+use crate::container;
+use crate::i18n::t;
+use crate::preset::Preset;
+use crate::sanitize::SanitizeRules;
+use crate::template::TemplateInput;
+use crate::verify::VerifySettings;
+use crate::{BatchTask, ProcessingState, TaskParams, generate_output_path, logging, media};
+
+// 两次扫描目录的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+// 界面中保留的最近处理记录条数
+const MAX_HISTORY: usize = 200;
+
+// 监视文件夹设置
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchSettings {
+    pub input_dir: String,
+    pub preset: String,         // 预设名称, 为空时使用默认设置
+    pub output_dir: String,     // 为空时使用预设中的输出目录
+    pub archive_dir: String,    // 处理成功后移入的目录, 为空时保留源文件
+    pub stable_secs: u64,       // 文件大小保持不变多久后视为写入完成
+    pub include_existing: bool, // 是否处理开始监视前已存在的文件
+}
+
+impl Default for WatchSettings {
+    fn default() -> Self {
+        Self {
+            input_dir: String::new(),
+            preset: String::new(),
+            output_dir: String::new(),
+            archive_dir: String::new(),
+            stable_secs: 10,
+            include_existing: false,
+        }
+    }
+}
+
+// 处理监视到的文件所需的全部设置
+#[derive(Clone)]
+pub struct WatchJob {
+    pub settings: WatchSettings,
+    pub preset: Preset,
+    pub sanitize: SanitizeRules,
+    pub global_args: ExtraArgs,
+    pub log_dir: String,
+    pub verify: VerifySettings,
+    pub collision_policy: CollisionPolicy,
+}
+
+impl WatchJob {
+    fn output_dir(&self) -> &str {
+        let dir = self.settings.output_dir.trim();
+        if dir.is_empty() {
+            &self.preset.output_dir
+        } else {
+            dir
+        }
+    }
+
+    fn plan_task(&self, input_path: &str) -> BatchTask {
+        let mut params = TaskParams::default();
+        self.preset.apply_to(&mut params);
+        let input = TemplateInput {
+            input_path,
+            params: &params,
+            index: 1,
+            segment: 1,
+        };
+        let output_path = generate_output_path(
+            &input,
+            self.output_dir(),
+            &self.preset.output_template,
+            &self.sanitize,
+        );
+        BatchTask {
+            input_path: input_path.to_string(),
+            log_path: logging::log_path_for(&output_path, &self.log_dir),
+            output_path,
+            params,
+            global_args: self.global_args.clone(),
+            output_action: OutputAction::default(),
+        }
+    }
+}
+
+// 监视状态, 供界面显示
+#[derive(Clone, Default)]
+pub struct WatchStatus {
+    pub message: String,
+    pub history: Vec<(String, TaskStatus)>, // (源文件, 处理结果)
+}
+
+// 用文件大小和修改时间判断文件是否仍在写入
+type Signature = (u64, Option<SystemTime>);
+
+struct Candidate {
+    signature: Signature,
+    since: Instant, // 上次发现变化的时间
+}
+
+// 扫描目录, 找出写入完成的新文件
+pub struct Watcher {
+    dir: PathBuf,
+    stable_for: Duration,
+    pending: HashMap<PathBuf, Candidate>,
+    handled: HashMap<PathBuf, Signature>, // 已处理过的文件, 内容变化后会重新处理
+    produced: HashSet<PathBuf>,           // 处理生成的输出文件, 永远不作为新文件处理
+}
+
+impl Watcher {
+    pub fn new(dir: &Path, stable_for: Duration, include_existing: bool) -> Watcher {
+        let mut watcher = Watcher {
+            dir: resolve_dir(dir),
+            stable_for,
+            pending: HashMap::new(),
+            handled: HashMap::new(),
+            produced: HashSet::new(),
+        };
+        if !include_existing {
+            watcher.handled = watcher.scan().into_iter().collect();
+        }
+        watcher
+    }
+
+    fn scan(&self) -> Vec<(PathBuf, Signature)> {
+        let Ok(entries) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        entries
+            .flatten()
+            .filter(|entry| !entry.file_name().to_string_lossy().starts_with('.'))
+            .filter_map(|entry| {
+                let path = entry.path();
+                let metadata = entry.metadata().ok()?;
+                if !metadata.is_file()
+                    || !media::has_media_extension(&path)
+                    || self.produced.contains(&path)
+                {
+                    return None;
+                }
+                Some((path, (metadata.len(), metadata.modified().ok())))
+            })
+            .collect()
+    }
+
+    // 记录处理生成的文件, 输出目录中的模板子目录等指回监视目录时不会反复处理
+    pub fn ignore(&mut self, path: &Path) {
+        if let Ok(path) = fs::canonicalize(path) {
+            self.produced.insert(path);
+        }
+    }
+
+    // 返回大小和修改时间在 stable_for 内没有变化的新文件, 每个文件只返回一次
+    pub fn poll(&mut self, now: Instant) -> Vec<PathBuf> {
+        self.produced.retain(|path| path.exists());
+        let files = self.scan();
+        // 已删除或移走的文件不再跟踪
+        self.pending
+            .retain(|path, _| files.iter().any(|(p, _)| p == path));
+        self.handled
+            .retain(|path, _| files.iter().any(|(p, _)| p == path));
+
+        let mut ready = Vec::new();
+        for (path, signature) in files {
+            if self.handled.get(&path) == Some(&signature) {
+                continue;
+            }
+            match self.pending.get(&path) {
+                Some(c) if c.signature == signature => {
+                    if signature.0 > 0 && now.duration_since(c.since) >= self.stable_for {
+                        self.pending.remove(&path);
+                        self.handled.insert(path.clone(), signature);
+                        ready.push(path);
+                    }
+                }
+                _ => {
+                    self.pending.insert(
+                        path,
+                        Candidate {
+                            signature,
+                            since: now,
+                        },
+                    );
+                }
+            }
+        }
+        ready.sort();
+        ready
+    }
+}
+
+// 在后台运行的监视任务
+pub struct WatchHandle {
+    stop: Arc<AtomicBool>,
+    pub status: Arc<Mutex<WatchStatus>>,
+    pub state: ProcessingState, // 当前文件的处理进度
+}
+
+impl WatchHandle {
+    // 停止监视, 并终止正在处理的文件
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+        *self.state.cancel_requested.lock().unwrap() = true;
+    }
+}
+
+// 比较目录前转换为绝对路径, 目录已存在时同时解析符号链接
+fn resolve_dir(dir: &Path) -> PathBuf {
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    fs::canonicalize(dir)
+        .or_else(|_| std::path::absolute(dir))
+        .unwrap_or_else(|_| dir.to_path_buf())
+}
+
+// 检查目录设置. 输出或归档到监视目录会把处理结果当作新文件再次处理
+pub fn validate(job: &WatchJob) -> Result<(), String> {
+    let input = job.settings.input_dir.trim();
+    if input.is_empty() || !Path::new(input).is_dir() {
+        return Err(t!("watch.input_missing", input));
+    }
+    let input_dir = resolve_dir(Path::new(input));
+    let archive = job.settings.archive_dir.trim();
+    if !archive.is_empty() && resolve_dir(Path::new(archive)) == input_dir {
+        return Err(t!("watch.archive_same").to_string());
+    }
+    if resolve_dir(Path::new(job.output_dir())) == input_dir {
+        return Err(t!("watch.output_same").to_string());
+    }
+    Ok(())
+}
+
+pub fn start(job: WatchJob) -> Result<WatchHandle, String> {
+    validate(&job)?;
+    let handle = WatchHandle {
+        stop: Arc::new(AtomicBool::new(false)),
+        status: Arc::new(Mutex::new(WatchStatus::default())),
+        state: ProcessingState::default(),
+    };
+    let stop = handle.stop.clone();
+    let status = handle.status.clone();
+    let state = handle.state.clone();
+    std::thread::spawn(move || run(&job, &stop, &status, &state));
+    Ok(handle)
+}
+
+// 持续监视目录直到 stop 被设置
+pub fn run(
+    job: &WatchJob,
+    stop: &AtomicBool,
+    status: &Mutex<WatchStatus>,
+    state: &ProcessingState,
+) {
+    let dir = PathBuf::from(job.settings.input_dir.trim());
+    let mut watcher = Watcher::new(
+        &dir,
+        Duration::from_secs(job.settings.stable_secs),
+        job.settings.include_existing,
+    );
+    let idle = t!("watch.watching", dir.display());
+    logging::log(&idle);
+    status.lock().unwrap().message = idle.clone();
+
+    while !stop.load(Ordering::Relaxed) {
+        for path in watcher.poll(Instant::now()) {
+            if stop.load(Ordering::Relaxed) {
+                break;
+            }
+            let input = path.to_string_lossy().into_owned();
+            status.lock().unwrap().message = t!("batch.processing", input);
+            let (result, output) = process_file(job, &input, state);
+            watcher.ignore(Path::new(&output));
+            let mut status = status.lock().unwrap();
+            status.history.push((input, result));
+            if status.history.len() > MAX_HISTORY {
+                status.history.remove(0);
+            }
+            status.message = idle.clone();
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+    logging::log(t!("watch.stopped", dir.display()));
+    status.lock().unwrap().message = t!("watch.stopped", dir.display());
+}
+
+// 返回处理结果和输出文件路径
+fn process_file(job: &WatchJob, input: &str, state: &ProcessingState) -> (TaskStatus, String) {
+    logging::log(t!("watch.found", input));
+    let mut tasks = vec![job.plan_task(input)];
+    // 无人值守时无法询问, 与命令行模式一样跳过已存在的输出
+    let policy = match job.collision_policy {
+        CollisionPolicy::Ask => CollisionPolicy::Skip,
+        policy => policy,
+    };
+    collision::resolve(&mut tasks, policy);
+
+    let task = &tasks[0];
+    let output = task.output_path.clone();
+    let problems: Vec<String> = command::build_ffmpeg_args(task)
+        .err()
+        .into_iter()
+        .chain(container::check(task))
+        .collect();
+    if !problems.is_empty() {
+        for problem in problems {
+            logging::log(format!("{}: {}", input, problem));
+        }
+        return (TaskStatus::Failed, output);
+    }
+
+    let options = batch::BatchOptions {
+        continue_on_error: true,
+        verify: job.verify.clone(),
+    };
+    batch::run_batch(tasks, state, &options);
+    let result = state.results.lock().unwrap()[0].clone();
+    match &result.error {
+        Some(e) => logging::log(t!("watch.failed", input, e)),
+        None => logging::log(format!("[{}] {}", result.status.label(), input)),
+    }
+
+    let archive = job.settings.archive_dir.trim();
+    if matches!(result.status, TaskStatus::Done | TaskStatus::Suspicious) && !archive.is_empty() {
+        match archive_source(Path::new(input), Path::new(archive)) {
+            Ok(to) => logging::log(t!("watch.archived", input, to.display())),
+            Err(e) => logging::log(t!("watch.archive_failed", input, e)),
+        }
+    }
+    (result.status, output)
+}
+
+// 把源文件移入归档目录, 同名文件已存在时追加编号
+fn archive_source(source: &Path, archive_dir: &Path) -> Result<PathBuf, String> {
+    fs::create_dir_all(archive_dir).map_err(|e| e.to_string())?;
+    let file_name = source.file_name().unwrap_or_default();
+    let mut target = archive_dir.join(file_name);
+    let stem = source
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let extension = source
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    let mut n = 1;
+    while target.exists() {
+        target = archive_dir.join(format!("{}_{}{}", stem, n, extension));
+        n += 1;
+    }
+    // 跨文件系统时无法直接重命名, 改为复制后删除
+    if fs::rename(source, &target).is_err() {
+        fs::copy(source, &target).map_err(|e| e.to_string())?;
+        fs::remove_file(source).map_err(|e| e.to_string())?;
+    }
+    Ok(target)
+}